@@ -0,0 +1,135 @@
+//! `ZkBackend` for kimchi, wrapping `new_index_for_test` / `ProverProof::create` / `verify`.
+//!
+//! The workload's `model_spec` network is proven with its real weights (`spec_circuit`),
+//! the same model the RISC Zero backend runs.
+use kimchi::circuits::wires::COLUMNS;
+use kimchi::groupmap::GroupMap;
+use kimchi::mina_curves::pasta::{Fp, Vesta};
+use kimchi::poly_commitment::{commitment::CommitmentCurve, evaluation_proof::OpeningProof};
+use kimchi::proof::ProverProof;
+use kimchi::prover_index::{testing::new_index_for_test, ProverIndex};
+use kimchi::verifier::verify;
+use kimchi::verifier_index::VerifierIndex;
+use zkbench::backend::{BackendError, Workload, ZkBackend};
+
+use crate::spec_circuit::build_spec_circuit;
+use crate::{BaseSponge, ScalarSponge};
+
+type Proof = ProverProof<Vesta, OpeningProof<Vesta>>;
+
+struct Setup {
+    index: ProverIndex<Vesta, OpeningProof<Vesta>>,
+    verifier_index: VerifierIndex<Vesta, OpeningProof<Vesta>>,
+    group_map: <Vesta as CommitmentCurve>::Map,
+    public: Vec<Fp>,
+    witness: [Vec<Fp>; COLUMNS],
+    /// Dequantized outputs from the witness.
+    outputs: Vec<Vec<f64>>,
+}
+
+#[derive(Default)]
+pub struct KimchiBackend {
+    setup: Option<Setup>,
+    metrics: Vec<(&'static str, f64)>,
+}
+
+impl KimchiBackend {
+    fn setup_ref(&self) -> Result<&Setup, BackendError> {
+        self.setup.as_ref().ok_or_else(|| "setup was not called".into())
+    }
+}
+
+impl ZkBackend for KimchiBackend {
+    type Proof = Proof;
+
+    fn name(&self) -> &'static str {
+        "kimchi"
+    }
+
+    fn setup(&mut self, workload: &Workload) -> Result<(), BackendError> {
+        // 레이어 크기는 Workload::load 에서 spec 과 맞는지 확인됨
+        let spec = workload.model()?;
+        let circuit = build_spec_circuit(&spec, &workload.input);
+        let outputs = circuit
+            .outputs
+            .iter()
+            .map(|row| row.iter().map(|&y| spec.dequantize(y)).collect())
+            .collect();
+        let (gates, witness, public) = (circuit.gates, circuit.witness, circuit.public);
+
+        let index = new_index_for_test(gates, public.len());
+        index
+            .verify(&witness, &public)
+            .map_err(|e| format!("witness does not satisfy the circuit: {:?}", e))?;
+        let verifier_index = index.verifier_index();
+
+        self.setup = Some(Setup {
+            index,
+            verifier_index,
+            group_map: <Vesta as CommitmentCurve>::Map::setup(),
+            public,
+            witness,
+            outputs,
+        });
+        Ok(())
+    }
+
+    fn prove(&mut self, _workload: &Workload) -> Result<Proof, BackendError> {
+        let setup = self.setup_ref()?;
+
+        // msm/ntt 카운터는 누적값이라 prove 전후 차이로 계산
+        let msm_time = kimchi::poly_commitment::commitment::get_msm_accumulated_time();
+        let msm_count = kimchi::poly_commitment::commitment::get_msm_function_call_count();
+        let ntt_time = kimchi::prover::get_ntt_accumulated_time();
+        let ntt_count = kimchi::prover::get_ntt_function_call_count();
+
+        let proof = ProverProof::create::<BaseSponge, ScalarSponge>(
+            &setup.group_map,
+            setup.witness.clone(),
+            &[],
+            &setup.index,
+        )
+        .map_err(|e| format!("proving failed: {:?}", e))?;
+
+        let metrics = vec![
+            ("gate_count", setup.index.cs.gates.len() as f64),
+            ("domain_size", setup.index.cs.domain.d1.size as f64),
+            (
+                "msm_time",
+                (kimchi::poly_commitment::commitment::get_msm_accumulated_time() - msm_time).as_secs_f64(),
+            ),
+            (
+                "msm_count",
+                (kimchi::poly_commitment::commitment::get_msm_function_call_count() - msm_count) as f64,
+            ),
+            ("ntt_time", (kimchi::prover::get_ntt_accumulated_time() - ntt_time).as_secs_f64()),
+            ("ntt_count", (kimchi::prover::get_ntt_function_call_count() - ntt_count) as f64),
+        ];
+        self.metrics = metrics;
+        Ok(proof)
+    }
+
+    fn verify(&self, proof: &Proof) -> Result<(), BackendError> {
+        let setup = self.setup_ref()?;
+        verify::<Vesta, BaseSponge, ScalarSponge, OpeningProof<Vesta>>(
+            &setup.group_map,
+            &setup.verifier_index,
+            proof,
+            &setup.public,
+        )
+        .map_err(|e| format!("verification failed: {:?}", e).into())
+    }
+
+    fn proof_size(&self, proof: &Proof) -> usize {
+        bincode::serialized_size(proof).map(|n| n as usize).unwrap_or(0)
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        self.metrics.clone()
+    }
+
+    // 출력은 공개 입력의 일부라 증명이 검증되면 그대로 믿을 수 있음
+    fn outputs(&self, _proof: &Proof) -> Result<Option<Vec<Vec<f64>>>, BackendError> {
+        Ok(Some(self.setup_ref()?.outputs.clone()))
+    }
+}
@@ -21,12 +21,25 @@ use kimchi::poly_commitment::{commitment::CommitmentCurve, evaluation_proof::Ope
 use std::array;
 use std::time::Instant;
 
+mod backend;
 mod mlp;
+mod spec_circuit;
+mod sweep;
+mod trace;
 
 use std::env;
+use std::str::FromStr;
 
+use backend::KimchiBackend;
+use mlp::create_layered_mlp_circuit;
 use mlp::create_mlp_circuit;
+use mlp::fill_in_layered_mlp_witness;
 use mlp::fill_in_mlp_witness;
+use sweep::{GridPoint, PointResult, SweepConfig, SweepWriter};
+use trace::Tracer;
+use zkbench::backend::Workload;
+use zkbench::csv::{ResultWriter, SharedBackendRow};
+use zkbench::stats::{scaling, Summary};
 
 type SpongeParams = PlonkSpongeConstantsKimchi;
 type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
@@ -40,71 +53,357 @@ fn main() {
     } else {
         1
     };
-    println!("run exp {}", exp);
+    // --trace <path>: 단계별 span을 Chrome trace JSON으로 저장
+    let trace_path = flag_value(&args, "--trace");
+    // --warmup N --runs N: 측정 전 버리는 실행 수와 측정 반복 수, --max-cv X: 분산 경고 기준
+    let warmup: usize = parse_flag(&args, "--warmup", 0);
+    let runs: usize = parse_flag(&args, "--runs", 1).max(1);
+    let max_cv: f64 = parse_flag(&args, "--max-cv", 0.05);
 
-    let depth = 2usize.pow(exp.try_into().unwrap());
-    println!("Creating proof for depth: {}", depth);
+    let mut tracer = Tracer::new();
 
-    // Input size: 4
-    let public = vec![Fp::from(3u8); 4];
+    // --threads 1,2,4 | 1..8: 스레드 수별로 rayon 풀을 새로 만들어 실행 (없으면 전역 풀)
+    let cli_threads: Vec<Option<usize>> = match flag_value(&args, "--threads") {
+        Some(spec) => {
+            let counts = parse_list("--threads", spec);
+            if counts.is_empty() || counts.contains(&0) {
+                eprintln!("--threads counts must be at least 1: {}", spec);
+                usage();
+            }
+            counts.into_iter().map(Some).collect()
+        }
+        None => vec![None],
+    };
+
+    // --workload <workload.json>: 공통 ZkBackend 드라이버로 실행, --out CSV에 한 행 추가
+    if let Some(workload_path) = flag_value(&args, "--workload") {
+        // 백엔드 CSV 에는 스레드 열이 없으므로 스레드 수별 실행은 exp/--sweep 에서만 지원
+        if cli_threads[0].is_some() {
+            eprintln!("--threads is not supported with --workload; set RAYON_NUM_THREADS instead");
+            usage();
+        }
+        let workload = Workload::load(workload_path).expect("bad workload");
+        let report = zkbench::backend::run(&mut KimchiBackend::default(), &workload, warmup, runs)
+            .expect("kimchi backend failed");
+        report.print();
+        let out = flag_value(&args, "--out").unwrap_or("backend_results.csv");
+        ResultWriter::backend(out, true)
+            .and_then(|mut writer| writer.write_backend(&report))
+            .expect("couldn't write backend CSV");
+        // --shared-out: 기존 framework,model,exp_num,... 형식에도 한 행
+        let shared_out = flag_value(&args, "--shared-out").unwrap_or("experiment_backends.csv");
+        ResultWriter::shared_backend(shared_out, true)
+            .and_then(|mut writer| writer.write_shared_backend(&SharedBackendRow(&report)))
+            .expect("couldn't write shared CSV");
+        // --dump-outputs <path>: equivcheck 용 출력 JSON
+        if let Some(path) = flag_value(&args, "--dump-outputs") {
+            report.write_outputs(path).expect("couldn't write outputs");
+        }
+        return;
+    }
+
+    // --sweep <config.json>: 설정 파일의 격자점마다 한 행씩 CSV로 저장
+    if let Some(config_path) = flag_value(&args, "--sweep") {
+        let config = SweepConfig::load(config_path).expect("bad sweep config");
+        let grid = config.grid();
+        let thread_counts = if config.threads.is_empty() {
+            cli_threads
+        } else {
+            config.threads.iter().copied().map(Some).collect()
+        };
+        let max_cv = config.max_cv.unwrap_or(max_cv);
+        let mut writer = SweepWriter::create(&config.out).expect("couldn't create sweep CSV");
+        for (n, point) in grid.iter().enumerate() {
+            println!("[{}/{}] {} weights={}", n + 1, grid.len(), point.label(), point.weights());
+            let results = bench_threads(
+                point,
+                Circuit::Layered,
+                &thread_counts,
+                config.warmup,
+                config.runs,
+                &mut tracer,
+            );
+            for result in &results {
+                println!(
+                    "  threads {} gate_count {} prove_time {:.3} (cv {:.3}) verify_time {:.3} (cv {:.3}){}",
+                    result.threads,
+                    result.gate_count,
+                    result.prove.mean,
+                    result.prove.cv(),
+                    result.verify.mean,
+                    result.verify.cv(),
+                    if result.is_noisy(max_cv) { "  HIGH VARIANCE" } else { "" },
+                );
+                writer.write(point, result, &results[0], max_cv).expect("couldn't write sweep CSV");
+            }
+        }
+        println!("sweep results: {}", config.out);
+    } else {
+        println!("run exp {}", exp);
+
+        let depth = 2usize.pow(exp.try_into().unwrap());
+        println!("Creating proof for depth: {}", depth);
+
+        // Input size: 4
+        let point = GridPoint {
+            profile: format!("exp{}", exp),
+            input: 4,
+            layers: vec![4; depth],
+            batch: 1,
+        };
+        // bench.sh 와 기존 ex*.csv 결과와 비교할 수 있도록 원래의 Mul+Add 서킷 사용
+        let results = bench_threads(&point, Circuit::Legacy, &cli_threads, warmup, runs, &mut tracer);
+
+        for result in &results {
+            if cli_threads[0].is_some() {
+                println!("threads: {}", result.threads);
+            }
+            // 기존 출력 형식 유지: prove_time / verify_time 은 평균
+            for (name, summary) in [("prove_time", &result.prove), ("verify_time", &result.verify)] {
+                println!("{}: {}", name, summary.mean);
+                if summary.n > 1 {
+                    println!(
+                        "  median {:.3} stddev {:.3} 95% CI {} cv {:.3} (n={}){}",
+                        summary.median,
+                        summary.stddev,
+                        summary.ci95_label(),
+                        summary.cv(),
+                        summary.n,
+                        if summary.is_noisy(max_cv) { "  HIGH VARIANCE" } else { "" },
+                    );
+                }
+            }
+        }
+
+        if results.len() > 1 {
+            let base = &results[0];
+            println!("threads  prove_time  speedup  efficiency  verify_time  speedup  efficiency");
+            for result in &results {
+                let (prove_speedup, prove_eff) =
+                    scaling(base.prove.mean, base.threads, result.prove.mean, result.threads);
+                let (verify_speedup, verify_eff) =
+                    scaling(base.verify.mean, base.threads, result.verify.mean, result.threads);
+                println!(
+                    "{:>7}  {:>10.3}  {:>7.2}  {:>10.2}  {:>11.3}  {:>7.2}  {:>10.2}",
+                    result.threads,
+                    result.prove.mean,
+                    prove_speedup,
+                    prove_eff,
+                    result.verify.mean,
+                    verify_speedup,
+                    verify_eff,
+                );
+            }
+        }
+    }
+
+    if let Some(path) = trace_path {
+        tracer.write(path).expect("couldn't write trace");
+        println!("trace: {}", path);
+    }
+}
+
+/// Circuit a benchmark point is proven with.
+#[derive(Clone, Copy)]
+enum Circuit {
+    /// `create_mlp_circuit`: one Mul+Add row per neuron of `input` width, the circuit behind
+    /// bench.sh and the `exp` results. Only `point.layers.len()` (depth) is used.
+    Legacy,
+    /// `create_layered_mlp_circuit`: one accumulate gate per weight, for sweeps.
+    Layered,
+}
+
+/// Runs `bench` once per thread count, each inside its own rayon pool.
+fn bench_threads(
+    point: &GridPoint,
+    circuit: Circuit,
+    thread_counts: &[Option<usize>],
+    warmup: usize,
+    runs: usize,
+    tracer: &mut Tracer,
+) -> Vec<PointResult> {
+    thread_counts
+        .iter()
+        .map(|&threads| match threads {
+            Some(n) => rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .expect("couldn't build rayon pool")
+                .install(|| bench(point, circuit, warmup, runs, tracer)),
+            None => bench(point, circuit, warmup, runs, tracer),
+        })
+        .collect()
+}
+
+/// Builds, indexes and proves one network shape `warmup + runs` times.
+fn bench(point: &GridPoint, circuit: Circuit, warmup: usize, runs: usize, tracer: &mut Tracer) -> PointResult {
+    let threads = rayon::current_num_threads();
+    tracer.next_track(&format!("{} threads={} setup", point.label(), threads));
+
+    let public = vec![Fp::from(3u8); point.input * point.batch];
     
     println!("public.len() {}", public.len());
-    let gates = create_mlp_circuit(public.len(), depth);
+    let gates = tracer.span("circuit_build", || match circuit {
+        Circuit::Legacy => create_mlp_circuit(public.len(), point.layers.len()),
+        Circuit::Layered => create_layered_mlp_circuit(point.input, &point.layers, point.batch),
+    });
 
     println!("gate_count: {}", gates.len());
+    let gate_count = gates.len();
 
     // create witness
-    let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); gates.len()]);
-    fill_in_mlp_witness(0, &mut witness, &public, public.len(), depth);
-
-    let index = new_index_for_test(gates, public.len());
-    // let verifier_index = index.verifier_index();
+    let witness = tracer.span("witness_generation", || {
+        let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); gates.len()]);
+        match circuit {
+            Circuit::Legacy => fill_in_mlp_witness(0, &mut witness, &public, public.len(), point.layers.len()),
+            Circuit::Layered => {
+                fill_in_layered_mlp_witness(0, &mut witness, &public, point.input, &point.layers, point.batch)
+            }
+        }
+        witness
+    });
 
-    // let verifier_index_serialize =
-    //     serde_json::to_string(&verifier_index).expect("couldn't serialize index");
+    let start_index = Instant::now();
+    let index = tracer.span("index_creation", || new_index_for_test(gates, public.len()));
+    let index_time = start_index.elapsed().as_secs_f64();
 
     // verify the circuit satisfiability by the computed witness
-    index.verify(&witness, &public).unwrap();
+    tracer.span("constraint_check", || index.verify(&witness, &public).unwrap());
 
     // add the proof to the batch
-    let group_map = <Vesta as CommitmentCurve>::Map::setup();
-
-    let start_proof = Instant::now();
-    let proof =
-        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index)
-            .unwrap();
-    println!("prove_time: {}", start_proof.elapsed().as_secs_f32());
-
-    let msm_time = kimchi::poly_commitment::commitment::get_msm_accumulated_time();
-    let msm_count = kimchi::poly_commitment::commitment::get_msm_function_call_count();
-    println!("msm_time: {:?}", msm_time.as_secs_f32());
-    println!("msm_count: {:?}", msm_count);
-
-    let ntt_time = kimchi::prover::get_ntt_accumulated_time();
-    let ntt_count = kimchi::prover::get_ntt_function_call_count();
-    println!("ntt_time: {:?}", ntt_time.as_secs_f32());
-    println!("ntt_count: {:?}", ntt_count);
-
-    // // deserialize the verifier index
-    // let mut verifier_index_deserialize: VerifierIndex<GroupAffine<VestaParameters>, _> =
-    //     serde_json::from_str(&verifier_index_serialize).unwrap();
-
-    // // add srs with lagrange bases
-    // let mut srs = SRS::<GroupAffine<VestaParameters>>::create(verifier_index.max_poly_size);
-    // srs.add_lagrange_basis(verifier_index.domain);
-    // verifier_index_deserialize.powers_of_alpha = index.powers_of_alpha;
-    // verifier_index_deserialize.linearization = index.linearization;
-    // verifier_index_deserialize.srs = std::sync::Arc::new(srs);
-
-    // // verify the proof
-    // let start = Instant::now();
-    // verify::<Vesta, BaseSponge, ScalarSponge, OpeningProof<Vesta>>(
-    //     &group_map,
-    //     &verifier_index_deserialize,
-    //     &proof,
-    //     &public,
-    // )
-    // .unwrap();
-    // println!("- time to verify: {}ms", start.elapsed().as_millis());
+    let group_map = tracer.span("group_map_setup", || <Vesta as CommitmentCurve>::Map::setup());
 
+    let verifier_index = tracer.span("verifier_index", || index.verifier_index());
+
+    let mut prove_times = vec![];
+    let mut verify_times = vec![];
+    for i in 0..warmup + runs {
+        let is_warmup = i < warmup;
+        let label = if is_warmup {
+            format!("warmup {}", i + 1)
+        } else {
+            format!("run {}", i - warmup + 1)
+        };
+        tracer.next_track(&format!("{} threads={} {}", point.label(), threads, label));
+
+        // msm/ntt 카운터는 누적값이라 실행 전후 차이로 계산
+        let msm_time_before = kimchi::poly_commitment::commitment::get_msm_accumulated_time();
+        let msm_count_before = kimchi::poly_commitment::commitment::get_msm_function_call_count();
+        let ntt_time_before = kimchi::prover::get_ntt_accumulated_time();
+        let ntt_count_before = kimchi::prover::get_ntt_function_call_count();
+
+        kimchi::prover::internal_traces::start_tracing();
+        let start_proof = Instant::now();
+        let proof = ProverProof::create::<BaseSponge, ScalarSponge>(
+            &group_map,
+            witness.clone(),
+            &[],
+            &index,
+        )
+        .unwrap();
+        let end_proof = Instant::now();
+        let prove_time = (end_proof - start_proof).as_secs_f64();
+
+        let msm_time = kimchi::poly_commitment::commitment::get_msm_accumulated_time() - msm_time_before;
+        let msm_count = kimchi::poly_commitment::commitment::get_msm_function_call_count() - msm_count_before;
+        let ntt_time = kimchi::prover::get_ntt_accumulated_time() - ntt_time_before;
+        let ntt_count = kimchi::prover::get_ntt_function_call_count() - ntt_count_before;
+
+        tracer.record(
+            "prove",
+            "phase",
+            start_proof,
+            end_proof,
+            serde_json::json!({
+                "msm_time": msm_time.as_secs_f64(),
+                "msm_count": msm_count,
+                "ntt_time": ntt_time.as_secs_f64(),
+                "ntt_count": ntt_count,
+            }),
+        );
+        if let Err(e) = tracer.record_prover_rounds(
+            start_proof,
+            end_proof,
+            &kimchi::prover::internal_traces::take_traces(),
+        ) {
+            eprintln!("warning: {}: no prover rounds in trace ({})", label, e);
+        }
+
+        // verify the proof
+        let start_verify = Instant::now();
+        tracer.span("verification", || {
+            verify::<Vesta, BaseSponge, ScalarSponge, OpeningProof<Vesta>>(
+                &group_map,
+                &verifier_index,
+                &proof,
+                &public,
+            )
+            .unwrap()
+        });
+        let verify_time = start_verify.elapsed().as_secs_f64();
+
+        println!(
+            "{}: prove_time {:.3} verify_time {:.3} msm_time {:.3} msm_count {:?} ntt_time {:.3} ntt_count {:?}",
+            label,
+            prove_time,
+            verify_time,
+            msm_time.as_secs_f32(),
+            msm_count,
+            ntt_time.as_secs_f32(),
+            ntt_count,
+        );
+        if !is_warmup {
+            prove_times.push(prove_time);
+            verify_times.push(verify_time);
+        }
+    }
+
+    PointResult {
+        threads,
+        gate_count,
+        domain_size: index.cs.domain.d1.size,
+        index_time,
+        prove: Summary::of(&prove_times).unwrap(),
+        verify: Summary::of(&verify_times).unwrap(),
+    }
+}
+
+// `--name value` 형태의 옵션 값
+fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+fn usage() -> ! {
+    eprintln!("usage: kimchi_exp [exp] [--warmup N] [--runs N] [--max-cv X] [--threads LIST] [--trace FILE]");
+    eprintln!("       kimchi_exp --sweep CONFIG [--max-cv X] [--threads LIST] [--trace FILE]");
+    eprintln!("       kimchi_exp --workload FILE [--warmup N] [--runs N] [--out CSV] [--shared-out CSV] [--dump-outputs FILE]");
+    std::process::exit(2);
+}
+
+// 잘못된 값은 panic 대신 사용법을 출력하고 종료
+fn parse_flag<T: FromStr>(args: &[String], name: &str, default: T) -> T {
+    match flag_value(args, name) {
+        None => default,
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            eprintln!("invalid value for {}: {}", name, value);
+            usage()
+        }),
+    }
+}
+
+// "1,2,4" 또는 "1..8" (양 끝 포함). 잘못된 값이면 사용법을 출력하고 종료
+fn parse_list(name: &str, spec: &str) -> Vec<usize> {
+    let parse = |s: &str| -> usize {
+        s.trim().parse().unwrap_or_else(|_| {
+            eprintln!("invalid value for {}: {}", name, spec);
+            usage()
+        })
+    };
+    if let Some((lo, hi)) = spec.split_once("..") {
+        return (parse(lo)..=parse(hi)).collect();
+    }
+    spec.split(',').map(parse).collect()
 }
@@ -1,4 +1,4 @@
-use ark_ff::FftField;
+use ark_ff::{FftField, Zero};
 use kimchi::circuits::wires::COLUMNS;
 use kimchi::{
     circuits::{gate::CircuitGate, polynomials::generic::GenericGateSpec, wires::Wire},
@@ -218,4 +218,117 @@ pub fn fill_in_mlp_witness<F: FftField>(
     // println!("final_output: {:?}", final_output);
     // witness[0][witness[0]] = final_output;     // 최종 출력값을 witness에 저장
     
-}
\ No newline at end of file
+}
+/// 임의 레이어 크기 + 배치용 dense MLP 서킷 (가중치/바이어스 0, 비용 측정용).
+///
+/// 행 배치: 공개 입력 `batch * input_size` 개, 샘플마다 레이어별 뉴런당
+/// fan-in 개의 누산 게이트(`acc = w_k * x_k + acc`, `spec_circuit`과 같은 단일 generic 게이트)와
+/// 바이어스 게이트 하나, 마지막으로 샘플마다 출력 행 하나.
+/// 따라서 은닉 행 수는 샘플당 `dense_weights(input_size, layers) + 뉴런 수`.
+pub fn create_layered_mlp_circuit(
+    input_size: usize,
+    layers: &[usize],
+    batch: usize,
+) -> Vec<CircuitGate<Fp>> {
+    let mut gates = vec![];
+    let mut gates_row = 0..; // 행 번호 생성기
+
+    // 입력 레이어 처리 (공개 입력은 앞쪽 행에 있어야 함)
+    for _ in 0..input_size * batch {
+        let r = gates_row.next().unwrap();
+        gates.push(CircuitGate::create_generic_gadget(
+            Wire::for_row(r),
+            GenericGateSpec::Pub,
+            None,
+        ));
+    }
+
+    // 샘플마다 은닉 레이어 반복: 가중치마다 누산 게이트 하나
+    for _ in 0..batch {
+        let mut fan_in = input_size;
+        for &width in layers {
+            for _ in 0..width {
+                for _ in 0..fan_in {
+                    let r = gates_row.next().unwrap();
+                    let weight = Fp::from(0u32);
+                    gates.push(CircuitGate::create_generic_gadget(
+                        Wire::for_row(r),
+                        GenericGateSpec::Add {
+                            left_coeff: Some(weight),
+                            right_coeff: None,
+                            output_coeff: None,
+                        },
+                        None,
+                    ));
+                }
+                let r = gates_row.next().unwrap();
+                let bias = Fp::from(0u32);
+                gates.push(CircuitGate::create_generic_gadget(
+                    Wire::for_row(r),
+                    GenericGateSpec::Plus(bias),
+                    None,
+                ));
+            }
+            fan_in = width;
+        }
+    }
+
+    // 출력 레이어: 샘플마다 한 행
+    for _ in 0..batch {
+        let r = gates_row.next().unwrap();
+        gates.push(CircuitGate::create_generic_gadget(
+            Wire::for_row(r),
+            GenericGateSpec::Pub,
+            None,
+        ));
+    }
+
+    gates
+}
+
+/// 샘플당 dense 가중치(곱셈) 수: `input * l_1 + l_1 * l_2 + ...`
+pub fn dense_weights(input_size: usize, layers: &[usize]) -> usize {
+    let mut fan_in = input_size;
+    let mut weights = 0;
+    for &width in layers {
+        weights += fan_in * width;
+        fan_in = width;
+    }
+    weights
+}
+
+/// `create_layered_mlp_circuit`용 witness. `public`은 샘플 순서대로 `batch * input_size`개.
+pub fn fill_in_layered_mlp_witness<F: FftField>(
+    start_row: usize,
+    witness: &mut [Vec<F>; COLUMNS],
+    public: &[F],
+    input_size: usize,
+    layers: &[usize],
+    batch: usize,
+) {
+    let mut witness_row = start_row..;
+
+    // 입력 레이어: 공개 입력 처리
+    for p in public.iter() {
+        let r = witness_row.next().unwrap();
+        witness[0][r] = *p;
+    }
+
+    // 은닉 레이어: 가중치/바이어스가 0이라 누산값과 뉴런 출력은 모두 0.
+    // 누산 게이트 왼쪽 칸에는 이전 레이어 값 x_k 를 둠 (계수 0)
+    for sample in public.chunks(input_size).take(batch) {
+        let mut x = sample.to_vec();
+        for &width in layers {
+            for _ in 0..width {
+                for &xk in &x {
+                    let r = witness_row.next().unwrap();
+                    witness[0][r] = xk;
+                }
+                // 바이어스 행: acc + 0 = 0
+                witness_row.next().unwrap();
+            }
+            x = vec![F::zero(); width];
+        }
+    }
+    // 출력 행은 0으로 둠
+}
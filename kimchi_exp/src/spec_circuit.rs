@@ -0,0 +1,228 @@
+//! kimchi circuit for a `ModelSpec` with its real weights, built together with the witness.
+//!
+//! Values are the fixed-point integers of `ModelSpec::forward_fixed`, negatives as `-|x|` in Fp.
+//! Each neuron is a chain of single generic gates, copy-constrained to the values they use:
+//!
+//! - `Add`:  `acc = w_k * x_k + acc`, one per input
+//! - `Plus`: `acc += b * 2^s`
+//! - `Add`:  `acc = q * 2^s + r` (rescale)
+//! - ReLU:   `Mul` `b * b = b`, then `Mul` `y = b * q`
+//!
+//! The remainder `r` and the sign behind the ReLU bit `b` are not range-checked, so this
+//! measures the cost of the honest computation rather than being a sound circuit.
+use ark_ff::Zero;
+use kimchi::circuits::gate::{CircuitGate, Connect};
+use kimchi::circuits::polynomials::generic::GenericGateSpec;
+use kimchi::circuits::wires::{Wire, COLUMNS};
+use kimchi::mina_curves::pasta::Fp;
+use model_spec::{Activation, ModelSpec};
+use std::array;
+
+type Cell = (usize, usize);
+
+/// A value in the witness and the cell it was first placed in.
+#[derive(Clone, Copy)]
+struct Var {
+    cell: Cell,
+    value: i128,
+}
+
+pub struct SpecCircuit {
+    pub gates: Vec<CircuitGate<Fp>>,
+    pub witness: [Vec<Fp>; COLUMNS],
+    /// Quantized inputs of every sample, then quantized outputs of every sample.
+    pub public: Vec<Fp>,
+    /// Fixed-point outputs per sample, as computed in the witness.
+    pub outputs: Vec<Vec<i64>>,
+}
+
+pub fn to_field(x: i128) -> Fp {
+    let magnitude = Fp::from(x.unsigned_abs());
+    if x < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+struct Builder {
+    gates: Vec<CircuitGate<Fp>>,
+    witness: [Vec<Fp>; COLUMNS],
+}
+
+impl Builder {
+    fn gate(&mut self, spec: GenericGateSpec<Fp>, l: i128, r: i128, o: i128) -> usize {
+        let row = self.gates.len();
+        self.gates.push(CircuitGate::create_generic_gadget(Wire::for_row(row), spec, None));
+        for (col, column) in self.witness.iter_mut().enumerate() {
+            column.push(match col {
+                0 => to_field(l),
+                1 => to_field(r),
+                2 => to_field(o),
+                _ => Fp::zero(),
+            });
+        }
+        row
+    }
+
+    // 같은 값을 쓰는 셀끼리 permutation 사이클로 연결
+    fn connect(&mut self, from: Cell, to: Cell) {
+        self.gates.connect_cell_pair(from, to);
+    }
+
+    fn neuron(&mut self, spec: &ModelSpec, x: &[Var], weights: &[i128], bias: i128, activation: Activation) -> Var {
+        let scale = 1i128 << spec.scale_bits();
+
+        // acc = w_0 * x_0 (r 는 계수 0이라 무관)
+        let mut value = weights[0] * x[0].value;
+        let row = self.gate(
+            GenericGateSpec::Add {
+                left_coeff: Some(to_field(weights[0])),
+                right_coeff: Some(Fp::zero()),
+                output_coeff: None,
+            },
+            x[0].value,
+            0,
+            value,
+        );
+        self.connect(x[0].cell, (row, 0));
+        let mut acc = Var { cell: (row, 2), value };
+
+        for (xk, &wk) in x.iter().zip(weights).skip(1) {
+            value = wk * xk.value + acc.value;
+            let row = self.gate(
+                GenericGateSpec::Add {
+                    left_coeff: Some(to_field(wk)),
+                    right_coeff: None,
+                    output_coeff: None,
+                },
+                xk.value,
+                acc.value,
+                value,
+            );
+            self.connect(xk.cell, (row, 0));
+            self.connect(acc.cell, (row, 1));
+            acc = Var { cell: (row, 2), value };
+        }
+
+        // 바이어스는 곱셈 결과와 같은 2^(2s) 스케일로 더함
+        let biased = acc.value + bias * scale;
+        let row = self.gate(GenericGateSpec::Plus(to_field(bias * scale)), acc.value, 0, biased);
+        self.connect(acc.cell, (row, 0));
+        let acc = Var { cell: (row, 2), value: biased };
+
+        // rescale: acc = q * 2^s + r
+        let (q, r) = (acc.value.div_euclid(scale), acc.value.rem_euclid(scale));
+        let row = self.gate(
+            GenericGateSpec::Add {
+                left_coeff: Some(to_field(scale)),
+                right_coeff: None,
+                output_coeff: None,
+            },
+            q,
+            r,
+            acc.value,
+        );
+        self.connect(acc.cell, (row, 2));
+        let q = Var { cell: (row, 0), value: q };
+
+        match activation {
+            Activation::Linear => q,
+            Activation::Relu => {
+                let bit = (q.value >= 0) as i128;
+                let bit_row = self.gate(
+                    GenericGateSpec::Mul {
+                        mul_coeff: None,
+                        output_coeff: None,
+                    },
+                    bit,
+                    bit,
+                    bit,
+                );
+                self.connect((bit_row, 0), (bit_row, 1));
+                self.connect((bit_row, 0), (bit_row, 2));
+
+                let row = self.gate(
+                    GenericGateSpec::Mul {
+                        mul_coeff: None,
+                        output_coeff: None,
+                    },
+                    bit,
+                    q.value,
+                    bit * q.value,
+                );
+                self.connect((bit_row, 0), (row, 0));
+                self.connect(q.cell, (row, 1));
+                Var {
+                    cell: (row, 2),
+                    value: bit * q.value,
+                }
+            }
+        }
+    }
+}
+
+/// Builds the circuit and witness for `spec` on the given input rows.
+pub fn build_spec_circuit(spec: &ModelSpec, inputs: &[Vec<f64>]) -> SpecCircuit {
+    let mut builder = Builder {
+        gates: vec![],
+        witness: array::from_fn(|_| vec![]),
+    };
+
+    // 공개 입력/출력 행은 앞쪽에 먼저 둠 (출력 값은 나중에 채움)
+    let mut sample_inputs = vec![];
+    for row in inputs {
+        let vars: Vec<Var> = row
+            .iter()
+            .map(|&x| {
+                let value = spec.quantize(x) as i128;
+                let r = builder.gate(GenericGateSpec::Pub, value, 0, 0);
+                Var { cell: (r, 0), value }
+            })
+            .collect();
+        sample_inputs.push(vars);
+    }
+    let output_rows: Vec<Vec<usize>> = inputs
+        .iter()
+        .map(|_| (0..spec.output_size()).map(|_| builder.gate(GenericGateSpec::Pub, 0, 0, 0)).collect())
+        .collect();
+
+    let layers: Vec<(Vec<Vec<i128>>, Vec<i128>)> = spec
+        .layers
+        .iter()
+        .map(|layer| {
+            // 뉴런 j 의 가중치 열
+            let columns = (0..layer.outputs)
+                .map(|j| layer.weights.iter().map(|row| spec.quantize(row[j]) as i128).collect())
+                .collect();
+            let bias = layer.bias.iter().map(|&b| spec.quantize(b) as i128).collect();
+            (columns, bias)
+        })
+        .collect();
+
+    let mut outputs = vec![];
+    for (x, out_rows) in sample_inputs.into_iter().zip(&output_rows) {
+        let mut x = x;
+        for ((columns, bias), layer) in layers.iter().zip(&spec.layers) {
+            x = columns
+                .iter()
+                .zip(bias)
+                .map(|(weights, &b)| builder.neuron(spec, &x, weights, b, layer.activation))
+                .collect();
+        }
+        for (var, &row) in x.iter().zip(out_rows) {
+            builder.witness[0][row] = to_field(var.value);
+            builder.connect(var.cell, (row, 0));
+        }
+        outputs.push(x.iter().map(|v| v.value as i64).collect());
+    }
+
+    let public_len = inputs.len() * (spec.input_size + spec.output_size());
+    let public = builder.witness[0][..public_len].to_vec();
+    SpecCircuit {
+        gates: builder.gates,
+        witness: builder.witness,
+        public,
+        outputs,
+    }
+}
@@ -0,0 +1,223 @@
+//! Parameter sweeps from a JSON config. Every grid point becomes one CSV row.
+//!
+//! ```json
+//! {
+//!   "widths": [4, 8, 16],
+//!   "depths": [2, 4, 8],
+//!   "batches": [1, 4],
+//!   "profiles": [{ "name": "pyramid", "input": 16, "layers": [16, 8, 4] }],
+//!   "threads": [1, 2, 4, 8],
+//!   "warmup": 1,
+//!   "runs": 3,
+//!   "max_cv": 0.05,
+//!   "out": "kimchi_sweep.csv"
+//! }
+//! ```
+//!
+//! `widths` x `depths` gives uniform networks (`input = width`, `depth` layers of
+//! `width`); each profile is used as-is. Both are crossed with `batches`, and each
+//! point is run once per entry of `threads` (rayon pool size; default: global pool).
+//! Speedup and efficiency are relative to the first thread count.
+//!
+//! The circuit is a dense MLP with one accumulate gate per weight, so `weights`
+//! (multiplications per sample) is the column to fit gate count and prove time against.
+//! Points whose prove or verify CV exceeds `max_cv` (default: `--max-cv`) are flagged `noisy`.
+use serde::Deserialize;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use crate::mlp::dense_weights;
+use zkbench::stats::{scaling, Summary};
+
+#[derive(Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub input: usize,
+    pub layers: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct SweepConfig {
+    #[serde(default)]
+    pub widths: Vec<usize>,
+    #[serde(default)]
+    pub depths: Vec<usize>,
+    #[serde(default = "default_batches")]
+    pub batches: Vec<usize>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    #[serde(default)]
+    pub threads: Vec<usize>,
+    #[serde(default)]
+    pub warmup: usize,
+    #[serde(default = "default_runs")]
+    pub runs: usize,
+    #[serde(default)]
+    pub max_cv: Option<f64>,
+    #[serde(default = "default_out")]
+    pub out: String,
+}
+
+fn default_batches() -> Vec<usize> {
+    vec![1]
+}
+
+fn default_runs() -> usize {
+    1
+}
+
+fn default_out() -> String {
+    "kimchi_sweep.csv".to_string()
+}
+
+/// One network shape to benchmark.
+#[derive(Clone, Debug)]
+pub struct GridPoint {
+    pub profile: String,
+    pub input: usize,
+    pub layers: Vec<usize>,
+    pub batch: usize,
+}
+
+impl GridPoint {
+    /// Multiplications per sample of the dense network.
+    pub fn weights(&self) -> usize {
+        dense_weights(self.input, &self.layers)
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{} in={} layers={} batch={}",
+            self.profile,
+            self.input,
+            join(&self.layers),
+            self.batch
+        )
+    }
+}
+
+impl SweepConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let config: SweepConfig = serde_json::from_str(&text)?;
+        if config.grid().is_empty() {
+            return Err("sweep config has no grid points (need widths and depths, or profiles)".into());
+        }
+        if config.runs == 0 {
+            return Err("runs must be at least 1".into());
+        }
+        if config.threads.contains(&0) {
+            return Err("threads must be at least 1".into());
+        }
+        Ok(config)
+    }
+
+    pub fn grid(&self) -> Vec<GridPoint> {
+        let mut shapes = vec![];
+        for &width in &self.widths {
+            for &depth in &self.depths {
+                shapes.push(("uniform".to_string(), width, vec![width; depth]));
+            }
+        }
+        for profile in &self.profiles {
+            shapes.push((profile.name.clone(), profile.input, profile.layers.clone()));
+        }
+
+        let mut points = vec![];
+        for &batch in &self.batches {
+            for (profile, input, layers) in &shapes {
+                points.push(GridPoint {
+                    profile: profile.clone(),
+                    input: *input,
+                    layers: layers.clone(),
+                    batch,
+                });
+            }
+        }
+        points
+    }
+}
+
+/// Measurements for one grid point.
+pub struct PointResult {
+    /// rayon pool size the point ran with.
+    pub threads: usize,
+    pub gate_count: usize,
+    pub domain_size: u64,
+    pub index_time: f64,
+    pub prove: Summary,
+    pub verify: Summary,
+}
+
+impl PointResult {
+    /// Prove or verify time varied more than `max_cv` across runs.
+    pub fn is_noisy(&self, max_cv: f64) -> bool {
+        self.prove.n > 1 && (self.prove.is_noisy(max_cv) || self.verify.is_noisy(max_cv))
+    }
+}
+
+pub const HEADER: &str = "profile,input_width,depth,max_width,layers,neurons,weights,batch,gate_count,domain_size,\
+index_time,prove_time,prove_stddev,prove_cv,verify_time,verify_stddev,verify_cv,runs,noisy,\
+threads,prove_speedup,prove_efficiency,verify_speedup,verify_efficiency";
+
+pub struct SweepWriter {
+    file: File,
+}
+
+impl SweepWriter {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", HEADER)?;
+        Ok(SweepWriter { file })
+    }
+
+    /// `base` is the same point at the first thread count.
+    pub fn write(
+        &mut self,
+        point: &GridPoint,
+        result: &PointResult,
+        base: &PointResult,
+        max_cv: f64,
+    ) -> io::Result<()> {
+        let (prove_speedup, prove_eff) =
+            scaling(base.prove.mean, base.threads, result.prove.mean, result.threads);
+        let (verify_speedup, verify_eff) =
+            scaling(base.verify.mean, base.threads, result.verify.mean, result.threads);
+        writeln!(
+            self.file,
+            "{},{},{},{},{},{},{},{},{},{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4},{},{},{},{:.3},{:.3},{:.3},{:.3}",
+            point.profile,
+            point.input,
+            point.layers.len(),
+            point.layers.iter().copied().max().unwrap_or(0),
+            join(&point.layers),
+            point.layers.iter().sum::<usize>(),
+            point.weights(),
+            point.batch,
+            result.gate_count,
+            result.domain_size,
+            result.index_time,
+            result.prove.mean,
+            result.prove.stddev,
+            result.prove.cv(),
+            result.verify.mean,
+            result.verify.stddev,
+            result.verify.cv(),
+            result.prove.n,
+            result.is_noisy(max_cv),
+            result.threads,
+            prove_speedup,
+            prove_eff,
+            verify_speedup,
+            verify_eff,
+        )?;
+        self.file.flush()
+    }
+}
+
+// CSV 칸 안에서 쓰도록 '-'로 연결 (예: 16-8-4)
+fn join(layers: &[usize]) -> String {
+    layers.iter().map(|w| w.to_string()).collect::<Vec<_>>().join("-")
+}
@@ -0,0 +1,159 @@
+//! Phase-level spans exported as Chrome trace-event JSON
+//! (open in chrome://tracing or https://ui.perfetto.dev).
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+struct Span {
+    name: String,
+    cat: &'static str,
+    start_us: f64,
+    dur_us: f64,
+    tid: u64,
+    args: Value,
+}
+
+pub struct Tracer {
+    origin: Instant,
+    origin_unix: f64,
+    tid: u64,
+    spans: Vec<Span>,
+    track_names: Vec<(u64, String)>,
+}
+
+impl Tracer {
+    pub fn new() -> Self {
+        Tracer {
+            origin: Instant::now(),
+            origin_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64(),
+            tid: 0,
+            spans: vec![],
+            track_names: vec![],
+        }
+    }
+
+    /// Following spans go on their own track (one per run / grid point).
+    pub fn set_track(&mut self, tid: u64, name: &str) {
+        self.tid = tid;
+        self.track_names.push((tid, name.to_string()));
+    }
+
+    /// Like `set_track` with the next unused track id.
+    pub fn next_track(&mut self, name: &str) {
+        let tid = self.track_names.iter().map(|(tid, _)| tid + 1).max().unwrap_or(0);
+        self.set_track(tid, name);
+    }
+
+    fn us_since_origin(&self, t: Instant) -> f64 {
+        t.duration_since(self.origin).as_secs_f64() * 1e6
+    }
+
+    /// Runs `f` and records it as a span.
+    pub fn span<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = f();
+        self.record(name, "phase", start, Instant::now(), Value::Null);
+        out
+    }
+
+    pub fn record(&mut self, name: &str, cat: &'static str, start: Instant, end: Instant, args: Value) {
+        let start_us = self.us_since_origin(start);
+        self.spans.push(Span {
+            name: name.to_string(),
+            cat,
+            start_us,
+            dur_us: self.us_since_origin(end) - start_us,
+            tid: self.tid,
+            args,
+        });
+    }
+
+    /// Splits the `[start, end]` prove window into prover rounds using kimchi's
+    /// `internal_tracing` checkpoints. Each checkpoint closes the round named after it.
+    /// Returns the number of rounds recorded; an error if the traces have none.
+    pub fn record_prover_rounds(&mut self, start: Instant, end: Instant, traces: &str) -> Result<usize, String> {
+        let mut checkpoints = parse_checkpoints(traces)?;
+        if checkpoints.is_empty() {
+            return Err("no prover checkpoints in internal_tracing output".to_string());
+        }
+        checkpoints.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let start_us = self.us_since_origin(start);
+        let end_us = self.us_since_origin(end);
+        let origin_us = self.origin_unix * 1e6;
+        let mut prev_us = start_us;
+        for (name, ts) in &checkpoints {
+            // 체크포인트는 UNIX epoch 기준 마이크로초, prove 구간 밖은 잘라냄
+            let ts_us = (*ts as f64 - origin_us).clamp(prev_us, end_us);
+            self.spans.push(Span {
+                name: name.clone(),
+                cat: "prover_round",
+                start_us: prev_us,
+                dur_us: ts_us - prev_us,
+                tid: self.tid,
+                args: Value::Null,
+            });
+            prev_us = ts_us;
+        }
+        Ok(checkpoints.len())
+    }
+
+    pub fn to_chrome_json(&self) -> Value {
+        let mut events: Vec<Value> = self
+            .track_names
+            .iter()
+            .map(|(tid, name)| {
+                json!({ "name": "thread_name", "ph": "M", "pid": 1, "tid": tid, "args": { "name": name } })
+            })
+            .collect();
+        for span in &self.spans {
+            let mut event = json!({
+                "name": span.name,
+                "cat": span.cat,
+                "ph": "X",
+                "ts": span.start_us,
+                "dur": span.dur_us,
+                "pid": 1,
+                "tid": span.tid,
+            });
+            if !span.args.is_null() {
+                event["args"] = span.args.clone();
+            }
+            events.push(event);
+        }
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let f = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(f, &self.to_chrome_json())?;
+        Ok(())
+    }
+}
+
+// internal_tracing 의 `take_traces()` 출력: {"<checkpoint>": [<UNIX epoch us>, <metadata|null>], ...}.
+// 이번 증명에서 지나지 않은 체크포인트는 타임스탬프가 0 이므로 제외
+fn parse_checkpoints(traces: &str) -> Result<Vec<(String, u64)>, String> {
+    let value: Value =
+        serde_json::from_str(traces).map_err(|e| format!("internal_tracing output is not JSON: {}", e))?;
+    let fields = value
+        .as_object()
+        .ok_or_else(|| "internal_tracing output is not an object".to_string())?;
+
+    let mut checkpoints = vec![];
+    for (name, entry) in fields {
+        let ts = entry
+            .get(0)
+            .and_then(Value::as_u64)
+            .ok_or_else(|| format!("checkpoint {} has no microsecond timestamp: {}", name, entry))?;
+        if ts != 0 {
+            checkpoints.push((name.clone(), ts));
+        }
+    }
+    Ok(checkpoints)
+}
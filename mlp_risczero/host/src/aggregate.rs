@@ -0,0 +1,144 @@
+//! `ZkBackend` that composes N inference receipts into one: setup proves the N
+//! `MLP_GUEST_ELF` receipts, prove runs `AGGREGATE_GUEST_ELF`, which `env::verify`s
+//! each of them as an assumption and commits the element-wise sum of their outputs.
+use methods::{AGGREGATE_GUEST_ELF, AGGREGATE_GUEST_ID, MLP_GUEST_ELF, MLP_GUEST_ID};
+use model_spec::journal::{hex, Hash};
+use model_spec::{InferenceMode, ModelSpec};
+use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, Receipt};
+use std::time::Instant;
+use zkbench::backend::{BackendError, Workload, ZkBackend};
+
+use crate::backend::{guest_env, Guest, GuestInput};
+
+pub struct AggregateBackend {
+    mode: InferenceMode,
+    count: usize,
+    spec: Option<ModelSpec>,
+    input: Vec<Vec<f64>>,
+    /// Succinct inference receipts, the assumptions of the aggregation proof.
+    receipts: Vec<Receipt>,
+    inner_prove_time: f64,
+    metrics: Vec<(&'static str, f64)>,
+}
+
+impl AggregateBackend {
+    pub fn new(mode: InferenceMode, count: usize) -> Self {
+        AggregateBackend {
+            mode,
+            count,
+            spec: None,
+            input: vec![],
+            receipts: vec![],
+            inner_prove_time: 0.0,
+            metrics: vec![],
+        }
+    }
+}
+
+impl ZkBackend for AggregateBackend {
+    type Proof = Receipt;
+
+    fn name(&self) -> &'static str {
+        match self.mode {
+            InferenceMode::Float => "risc0_aggregate_float",
+            InferenceMode::Fixed => "risc0_aggregate_fixed",
+        }
+    }
+
+    /// 추론 영수증 N개를 succinct 로 증명해 둠 (합성 시 assumption 해소 비용을 줄이기 위해)
+    fn setup(&mut self, workload: &Workload) -> Result<(), BackendError> {
+        let spec = workload.model()?;
+
+        let prover = default_prover();
+        let start = Instant::now();
+        self.receipts = (0..self.count)
+            .map(|_| {
+                let (env, _) = guest_env(&spec, &workload.input, self.mode, Guest::Tensor)?;
+                let info = prover.prove_with_opts(env, MLP_GUEST_ELF, &ProverOpts::succinct())?;
+                Ok(info.receipt)
+            })
+            .collect::<Result<_, BackendError>>()?;
+        self.inner_prove_time = start.elapsed().as_secs_f64();
+        self.spec = Some(spec);
+        self.input = workload.input.clone();
+        Ok(())
+    }
+
+    fn prove(&mut self, _workload: &Workload) -> Result<Receipt, BackendError> {
+        let journals: Vec<&Vec<u8>> = self.receipts.iter().map(|r| &r.journal.bytes).collect();
+        let mut builder = ExecutorEnv::builder();
+        for receipt in &self.receipts {
+            builder.add_assumption(receipt.clone());
+        }
+        builder
+            .write(&MLP_GUEST_ID)?
+            .write(&self.mode)?
+            .write(&journals)?;
+        let env = builder.build()?;
+
+        // succinct 로 증명해야 assumption 이 해소된 단일 영수증이 나옴
+        let info =
+            default_prover().prove_with_opts(env, AGGREGATE_GUEST_ELF, &ProverOpts::succinct())?;
+        self.metrics = vec![
+            ("receipts", self.count as f64),
+            ("inner_prove_time", self.inner_prove_time),
+            ("segments", info.stats.segments as f64),
+            ("total_cycles", info.stats.total_cycles as f64),
+            ("user_cycles", info.stats.user_cycles as f64),
+        ];
+        Ok(info.receipt)
+    }
+
+    fn verify(&self, receipt: &Receipt) -> Result<(), BackendError> {
+        receipt.verify(AGGREGATE_GUEST_ID)?;
+        Ok(())
+    }
+
+    fn proof_size(&self, receipt: &Receipt) -> usize {
+        bincode::serialized_size(receipt)
+            .map(|n| n as usize)
+            .unwrap_or(0)
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        self.metrics.clone()
+    }
+
+    /// 집계 영수증이 가리키는 image ID, 모델 해시, 개수를 확인한 뒤 출력 합을 f64 로 반환
+    fn outputs(&self, receipt: &Receipt) -> Result<Option<Vec<Vec<f64>>>, BackendError> {
+        let spec = self.spec.as_ref().ok_or("setup was not called")?;
+        let (image_id, model_hash, count, sum) = match self.mode {
+            InferenceMode::Float => receipt
+                .journal
+                .decode::<([u32; 8], Hash, u32, Vec<Vec<f64>>)>()?,
+            InferenceMode::Fixed => {
+                let (image_id, model_hash, count, sum) =
+                    receipt
+                        .journal
+                        .decode::<([u32; 8], Hash, u32, Vec<Vec<i64>>)>()?;
+                let sum = sum
+                    .iter()
+                    .map(|row| row.iter().map(|&y| spec.dequantize(y)).collect())
+                    .collect();
+                (image_id, model_hash, count, sum)
+            }
+        };
+        if image_id != MLP_GUEST_ID || count as usize != self.count {
+            return Err(format!(
+                "aggregate journal covers {} receipts of image {:?}, expected {} of {:?}",
+                count, image_id, self.count, MLP_GUEST_ID
+            )
+            .into());
+        }
+        let sent = GuestInput::new(spec, &self.input, self.mode, Guest::Tensor)?.model_hash();
+        if model_hash != sent {
+            return Err(format!(
+                "aggregate journal model hash {} does not match the model sent ({})",
+                hex(&model_hash),
+                hex(&sent)
+            )
+            .into());
+        }
+        Ok(Some(sum))
+    }
+}
@@ -0,0 +1,597 @@
+//! `ZkBackend` for RISC Zero, wrapping `default_prover` and receipt verification.
+use methods::{MLP_GUEST_ELF, MLP_GUEST_ID, MLP_GUEST_LEGACY_ELF};
+use model_spec::journal::{hex, CycleReport, Hash, InferenceJournal};
+use model_spec::{binary, Activation, InferenceMode, ModelSpec};
+use risc0_zkvm::serde::{from_slice, to_vec};
+use risc0_zkvm::sha::{Impl, Sha256};
+use risc0_zkvm::{default_prover, ExecutorEnv, ExecutorImpl, Journal, ProverOpts, Receipt};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use zkbench::backend::{BackendError, Workload, ZkBackend};
+use zkbench::csv::{LayerProfileRow, ResultWriter};
+use zkbench::stats::Summary;
+
+use crate::receipt::SavedReceipt;
+
+/// Written by `generate_data`; used when the workload has no spec of its own.
+pub const SPEC_PATH: &str = "./model_data/mlp_spec.json";
+pub const INPUT_PATH: &str = "./model_data/mlp_input.json";
+
+/// Workload built from the `model_data` spec and input files.
+pub fn default_workload() -> Result<Workload, BackendError> {
+    let spec = ModelSpec::load(SPEC_PATH)?;
+    let input = binary::load_input(INPUT_PATH)?;
+
+    Ok(Workload {
+        name: spec.name.clone(),
+        layers: spec.layer_sizes(),
+        input,
+        input_file: None,
+        spec: PathBuf::from(SPEC_PATH),
+    })
+}
+
+/// Inference guest to run. `Smartcore` is the old `DenseMatrix` guest, kept only so
+/// executor cycle counts can be compared with the flat-tensor guest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Guest {
+    Tensor,
+    Smartcore,
+}
+
+impl Guest {
+    pub fn elf(self) -> &'static [u8] {
+        match self {
+            Guest::Tensor => MLP_GUEST_ELF,
+            Guest::Smartcore => MLP_GUEST_LEGACY_ELF,
+        }
+    }
+}
+
+impl FromStr for Guest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "tensor" => Ok(Guest::Tensor),
+            "smartcore" | "legacy" => Ok(Guest::Smartcore),
+            _ => Err(format!("unknown guest {} (tensor or smartcore)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Guest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Guest::Tensor => write!(f, "tensor"),
+            Guest::Smartcore => write!(f, "smartcore"),
+        }
+    }
+}
+
+/// Model and input in the guest's representation for `mode`: binary files for the tensor
+/// guest, or serde words (f64 weights and rows, or the quantized model and fixed-point rows)
+/// for the smartcore guest, which streams them sample by sample.
+pub struct GuestInput {
+    pub model: ModelInput,
+    pub samples: SampleInput,
+}
+
+/// The model as the guest receives it.
+pub enum ModelInput {
+    /// A `model_spec::binary` file (`f64` or fixed-point), for the tensor guest.
+    Binary(Vec<u8>),
+    /// serde words, for the smartcore guest.
+    Words(Vec<u32>),
+}
+
+/// The input rows as the guest receives them.
+pub enum SampleInput {
+    /// An `f64` input file, byte for byte what `generate_data` writes as `mlp_input.bin`; the
+    /// tensor guest quantizes the rows itself in fixed mode.
+    Binary(Vec<u8>),
+    /// One serde word vector per sample, for the smartcore guest.
+    Words(Vec<Vec<u32>>),
+}
+
+impl GuestInput {
+    pub fn new(
+        spec: &ModelSpec,
+        input: &[Vec<f64>],
+        mode: InferenceMode,
+        guest: Guest,
+    ) -> Result<Self, BackendError> {
+        if guest == Guest::Tensor {
+            let model = match mode {
+                InferenceMode::Float => spec.to_bytes(),
+                InferenceMode::Fixed => spec.to_fixed_bytes(),
+            };
+            return Ok(GuestInput {
+                model: ModelInput::Binary(model),
+                samples: SampleInput::Binary(binary::encode_input(input)?),
+            });
+        }
+        // smartcore 게스트는 ModelSpec 과 샘플 행을 serde 워드로 받음
+        Ok(match mode {
+            InferenceMode::Float => GuestInput {
+                model: ModelInput::Words(to_vec(spec)?),
+                samples: SampleInput::Words(input.iter().map(to_vec).collect::<Result<_, _>>()?),
+            },
+            InferenceMode::Fixed => {
+                let quantized: Vec<Vec<i64>> = input
+                    .iter()
+                    .map(|row| row.iter().map(|&x| spec.quantize(x)).collect())
+                    .collect();
+                GuestInput {
+                    model: ModelInput::Words(to_vec(&spec.quantized())?),
+                    samples: SampleInput::Words(quantized.iter().map(to_vec).collect::<Result<_, _>>()?),
+                }
+            }
+        })
+    }
+
+    /// For a binary model this is the SHA-256 of the file bytes, as `sha256sum` prints it.
+    pub fn model_hash(&self) -> Hash {
+        match &self.model {
+            ModelInput::Binary(bytes) => sha256_bytes(bytes),
+            ModelInput::Words(words) => sha256(words),
+        }
+    }
+
+    /// For the tensor guest this is the SHA-256 of the input file bytes, so `sha256sum
+    /// mlp_input.bin` reproduces it. The smartcore guest hashes the sample words back to back.
+    pub fn input_hash(&self) -> Hash {
+        match &self.samples {
+            SampleInput::Binary(bytes) => sha256_bytes(bytes),
+            SampleInput::Words(samples) => sha256(&samples.concat()),
+        }
+    }
+}
+
+fn sha256_bytes(bytes: &[u8]) -> Hash {
+    Impl::hash_bytes(bytes).as_bytes().try_into().unwrap()
+}
+
+fn sha256(words: &[u32]) -> Hash {
+    Impl::hash_words(words).as_bytes().try_into().unwrap()
+}
+
+fn check_hash(what: &str, got: &Hash, sent: &Hash) -> Result<(), BackendError> {
+    if got != sent {
+        return Err(format!(
+            "journal {} hash {} does not match the {} sent ({})",
+            what,
+            hex(got),
+            what,
+            hex(sent)
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Guest stdout, which carries only the `CycleReport` written after the journal commit.
+#[derive(Clone, Default)]
+pub struct CycleLog(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CycleLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CycleLog {
+    pub fn report(&self) -> Result<CycleReport, BackendError> {
+        let bytes = self.0.lock().unwrap();
+        Ok(from_slice::<CycleReport, u8>(&bytes)?)
+    }
+}
+
+/// Guest input: the mode, the model (a byte length and the raw binary file, or serde words),
+/// then the input (a byte length and the raw input file, or the sample count and one word
+/// vector per sample). The returned log collects the guest's cycle report.
+pub fn guest_env(
+    spec: &ModelSpec,
+    input: &[Vec<f64>],
+    mode: InferenceMode,
+    guest: Guest,
+) -> Result<(ExecutorEnv<'static>, CycleLog), BackendError> {
+    let words = GuestInput::new(spec, input, mode, guest)?;
+    let log = CycleLog::default();
+    let mut builder = ExecutorEnv::builder();
+    builder.write(&mode)?;
+    match &words.model {
+        ModelInput::Binary(bytes) => builder.write(&(bytes.len() as u32))?.write_slice(bytes),
+        ModelInput::Words(model) => builder.write(model)?,
+    };
+    match &words.samples {
+        SampleInput::Binary(bytes) => {
+            builder.write(&(bytes.len() as u32))?.write_slice(bytes);
+        }
+        SampleInput::Words(samples) => {
+            builder.write(&(samples.len() as u32))?;
+            for sample in samples {
+                builder.write(sample)?;
+            }
+        }
+    }
+    builder.stdout(log.clone());
+    Ok((builder.build()?, log))
+}
+
+/// Cycle metrics for the backend CSV. The per-layer breakdown is summed into
+/// `layer_cycles_per_mac`; `layer_profile` has it layer by layer.
+pub fn cycle_metrics(spec: &ModelSpec, cycles: &CycleReport) -> Vec<(&'static str, f64)> {
+    let samples: Vec<f64> = cycles.sample_cycles.iter().map(|&c| c as f64).collect();
+    let summary = Summary::of(&samples);
+    let layers = layer_profile(spec, cycles);
+    let macs: usize = layers.iter().map(LayerProfile::macs).sum();
+    let layer_cycles: f64 = layers.iter().map(|l| l.cycles).sum();
+    vec![
+        ("samples", samples.len() as f64),
+        ("load_cycles", cycles.load_cycles as f64),
+        ("sample_cycles_mean", summary.map_or(0.0, |s| s.mean)),
+        ("sample_cycles_max", summary.map_or(0.0, |s| s.max)),
+        ("macs_per_sample", macs as f64),
+        ("layer_cycles_per_mac", layer_cycles / macs.max(1) as f64),
+    ]
+}
+
+/// One layer's forward cost in the guest, next to its shape.
+#[derive(Clone, Debug)]
+pub struct LayerProfile {
+    pub inputs: usize,
+    pub outputs: usize,
+    pub activation: Activation,
+    /// Cycles per sample, averaged over the samples.
+    pub cycles: f64,
+}
+
+impl LayerProfile {
+    /// Multiply-accumulates per sample, one per weight.
+    pub fn macs(&self) -> usize {
+        self.inputs * self.outputs
+    }
+
+    pub fn cycles_per_mac(&self) -> f64 {
+        self.cycles / self.macs().max(1) as f64
+    }
+}
+
+/// Joins the guest's per-layer cycle totals with the layer shapes in `spec`.
+pub fn layer_profile(spec: &ModelSpec, cycles: &CycleReport) -> Vec<LayerProfile> {
+    let samples = cycles.sample_cycles.len().max(1) as f64;
+    spec.layers
+        .iter()
+        .zip(&cycles.layer_cycles)
+        .map(|(layer, &c)| LayerProfile {
+            inputs: layer.inputs(),
+            outputs: layer.outputs,
+            activation: layer.activation,
+            cycles: c as f64 / samples,
+        })
+        .collect()
+}
+
+/// Prints one line per layer: shape, MACs, cycles per sample, share of all layer cycles
+/// and cycles per MAC.
+pub fn print_layer_profile(layers: &[LayerProfile]) {
+    let total: f64 = layers.iter().map(|l| l.cycles).sum();
+    println!("  layers (cycles per sample):");
+    for (i, layer) in layers.iter().enumerate() {
+        println!(
+            "    {}: {}x{} {}, {} MACs, {:.0} cycles ({:.1}%), {:.1} cycles/MAC",
+            i,
+            layer.inputs,
+            layer.outputs,
+            layer.activation,
+            layer.macs(),
+            layer.cycles,
+            layer.cycles / total.max(1.0) * 100.0,
+            layer.cycles_per_mac()
+        );
+    }
+}
+
+/// Appends the profile to a CSV (`zkbench::csv::LAYER_PROFILE_HEADER`), one row per layer.
+pub fn write_layer_profile(
+    path: impl AsRef<Path>,
+    workload: &str,
+    mode: InferenceMode,
+    guest: Guest,
+    layers: &[LayerProfile],
+) -> io::Result<()> {
+    let guest = guest.to_string();
+    let mut writer = ResultWriter::layer_profile(path, true)?;
+    for (i, layer) in layers.iter().enumerate() {
+        writer.write_layer_profile(&LayerProfileRow {
+            workload,
+            mode,
+            guest: &guest,
+            layer: i,
+            inputs: layer.inputs,
+            outputs: layer.outputs,
+            activation: layer.activation,
+            cycles: layer.cycles,
+        })?;
+    }
+    Ok(())
+}
+
+/// Journal outputs as f64, dequantizing fixed-point results, after checking that the
+/// committed model and input hashes match what `guest_env` sends for `spec` and `input`.
+pub fn decode_outputs(
+    journal: &Journal,
+    spec: &ModelSpec,
+    input: &[Vec<f64>],
+    mode: InferenceMode,
+    guest: Guest,
+) -> Result<Vec<Vec<f64>>, BackendError> {
+    let (model_hash, input_hash, outputs) = match mode {
+        InferenceMode::Float => {
+            let journal: InferenceJournal<f64> = journal.decode()?;
+            (journal.model_hash, journal.input_hash, journal.outputs)
+        }
+        InferenceMode::Fixed => {
+            let journal: InferenceJournal<i64> = journal.decode()?;
+            (journal.model_hash, journal.input_hash, journal.dequantize())
+        }
+    };
+    let sent = GuestInput::new(spec, input, mode, guest)?;
+    check_hash("model", &model_hash, &sent.model_hash())?;
+    check_hash("input", &input_hash, &sent.input_hash())?;
+    Ok(outputs)
+}
+
+/// Executor-only run: no proof, just the trace statistics.
+pub struct ExecuteReport {
+    pub mode: InferenceMode,
+    pub guest: Guest,
+    pub elapsed: Duration,
+    pub segments: usize,
+    pub total_cycles: u64,
+    pub user_cycles: u64,
+    pub paging_cycles: u64,
+    pub reserved_cycles: u64,
+    pub cycles: CycleReport,
+    pub layers: Vec<LayerProfile>,
+    pub outputs: Vec<Vec<f64>>,
+}
+
+impl ExecuteReport {
+    pub fn print(&self) {
+        println!("mode: {} guest: {} (executor only)", self.mode, self.guest);
+        println!("  execute_time: {:.3}", self.elapsed.as_secs_f64());
+        println!("  segments: {}", self.segments);
+        println!("  total_cycles: {}", self.total_cycles);
+        println!("  user_cycles: {}", self.user_cycles);
+        println!("  paging_cycles: {}", self.paging_cycles);
+        println!("  reserved_cycles: {}", self.reserved_cycles);
+        println!("  load_cycles: {}", self.cycles.load_cycles);
+        println!("  sample_cycles: {:?}", self.cycles.sample_cycles);
+        print_layer_profile(&self.layers);
+        println!("  outputs: {:?}", self.outputs);
+    }
+}
+
+pub fn execute(
+    spec: &ModelSpec,
+    input: &[Vec<f64>],
+    mode: InferenceMode,
+    guest: Guest,
+) -> Result<ExecuteReport, BackendError> {
+    let (env, log) = guest_env(spec, input, mode, guest)?;
+    let start = Instant::now();
+    let session = ExecutorImpl::from_elf(env, guest.elf())?.run()?;
+    let elapsed = start.elapsed();
+
+    let journal = session
+        .journal
+        .as_ref()
+        .ok_or("guest did not commit a journal")?;
+    let stats = session.stats();
+    let cycles = log.report()?;
+    Ok(ExecuteReport {
+        mode,
+        guest,
+        elapsed,
+        segments: stats.segments,
+        total_cycles: stats.total_cycles,
+        user_cycles: stats.user_cycles,
+        paging_cycles: stats.paging_cycles,
+        reserved_cycles: stats.reserved_cycles,
+        layers: layer_profile(spec, &cycles),
+        cycles,
+        outputs: decode_outputs(journal, spec, input, mode, guest)?,
+    })
+}
+
+/// Which receipt the prover hands back. Succinct and Groth16 receipts are compressed
+/// from the composite receipt of the same execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiptKind {
+    /// One STARK per segment.
+    Composite,
+    /// Segments recursively joined into a single STARK.
+    Succinct,
+    /// Succinct receipt wrapped in a Groth16 SNARK; needs the local Groth16 prover (x86 + Docker).
+    Groth16,
+}
+
+impl ReceiptKind {
+    pub const ALL: [ReceiptKind; 3] = [
+        ReceiptKind::Composite,
+        ReceiptKind::Succinct,
+        ReceiptKind::Groth16,
+    ];
+
+    fn opts(self) -> ProverOpts {
+        match self {
+            ReceiptKind::Composite => ProverOpts::composite(),
+            ReceiptKind::Succinct => ProverOpts::succinct(),
+            ReceiptKind::Groth16 => ProverOpts::groth16(),
+        }
+    }
+}
+
+impl FromStr for ReceiptKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "composite" => Ok(ReceiptKind::Composite),
+            "succinct" => Ok(ReceiptKind::Succinct),
+            "groth16" | "compressed" => Ok(ReceiptKind::Groth16),
+            _ => Err(format!(
+                "unknown receipt kind {} (composite, succinct or groth16)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReceiptKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReceiptKind::Composite => write!(f, "composite"),
+            ReceiptKind::Succinct => write!(f, "succinct"),
+            ReceiptKind::Groth16 => write!(f, "groth16"),
+        }
+    }
+}
+
+pub struct Risc0Backend {
+    mode: InferenceMode,
+    kind: ReceiptKind,
+    spec: Option<ModelSpec>,
+    input: Vec<Vec<f64>>,
+    metrics: Vec<(&'static str, f64)>,
+    /// Per-layer cycles of the last `prove`.
+    layers: Vec<LayerProfile>,
+    /// Receipt of the last `prove`, kept so it can be saved after the driver drops its copy.
+    receipt: Option<Receipt>,
+}
+
+impl Risc0Backend {
+    pub fn new(mode: InferenceMode) -> Self {
+        Risc0Backend {
+            mode,
+            kind: ReceiptKind::Composite,
+            spec: None,
+            input: vec![],
+            metrics: vec![],
+            layers: vec![],
+            receipt: None,
+        }
+    }
+
+    pub fn with_receipt_kind(mut self, kind: ReceiptKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn layers(&self) -> &[LayerProfile] {
+        &self.layers
+    }
+
+    /// Saves the last receipt with its image ID; returns the file size in bytes.
+    pub fn save_receipt(&self, path: impl AsRef<Path>) -> Result<usize, BackendError> {
+        let receipt = self.receipt.clone().ok_or("nothing has been proven yet")?;
+        SavedReceipt::new(receipt, self.mode).save(path)
+    }
+}
+
+impl ZkBackend for Risc0Backend {
+    type Proof = Receipt;
+
+    fn name(&self) -> &'static str {
+        // composite 는 예전 이름 그대로 두어 기존 CSV 와 이어지게 함
+        match (self.mode, self.kind) {
+            (InferenceMode::Float, ReceiptKind::Composite) => "risc0_float",
+            (InferenceMode::Fixed, ReceiptKind::Composite) => "risc0_fixed",
+            (InferenceMode::Float, ReceiptKind::Succinct) => "risc0_float_succinct",
+            (InferenceMode::Fixed, ReceiptKind::Succinct) => "risc0_fixed_succinct",
+            (InferenceMode::Float, ReceiptKind::Groth16) => "risc0_float_groth16",
+            (InferenceMode::Fixed, ReceiptKind::Groth16) => "risc0_fixed_groth16",
+        }
+    }
+
+    /// 가중치는 스펙 파일에서 읽음 (레이어 크기는 Workload::load 에서 확인됨)
+    fn setup(&mut self, workload: &Workload) -> Result<(), BackendError> {
+        let spec = workload.model()?;
+
+        self.spec = Some(spec);
+        self.input = workload.input.clone();
+        Ok(())
+    }
+
+    fn prove(&mut self, _workload: &Workload) -> Result<Receipt, BackendError> {
+        let spec = self.spec.as_ref().ok_or("setup was not called")?;
+        let (env, log) = guest_env(spec, &self.input, self.mode, Guest::Tensor)?;
+        let prover = default_prover();
+        let info = prover.prove(env, MLP_GUEST_ELF)?;
+
+        self.metrics = vec![
+            ("segments", info.stats.segments as f64),
+            ("total_cycles", info.stats.total_cycles as f64),
+            ("user_cycles", info.stats.user_cycles as f64),
+            ("paging_cycles", info.stats.paging_cycles as f64),
+            ("reserved_cycles", info.stats.reserved_cycles as f64),
+        ];
+        let cycles = log.report()?;
+        self.metrics.extend(cycle_metrics(spec, &cycles));
+        self.layers = layer_profile(spec, &cycles);
+
+        // 같은 실행의 composite 영수증을 압축 (prove_time 에는 압축 시간도 포함됨)
+        let receipt = match self.kind {
+            ReceiptKind::Composite => info.receipt,
+            kind => {
+                let start = Instant::now();
+                let receipt = prover.compress(&kind.opts(), &info.receipt)?;
+                self.metrics
+                    .push(("compress_time", start.elapsed().as_secs_f64()));
+                receipt
+            }
+        };
+        self.receipt = Some(receipt.clone());
+        Ok(receipt)
+    }
+
+    /// 영수증 검증만 측정함. journal 의 모델/입력 해시 확인은 측정 구간 밖의 `outputs` 에서 함
+    fn verify(&self, receipt: &Receipt) -> Result<(), BackendError> {
+        receipt.verify(MLP_GUEST_ID)?;
+        Ok(())
+    }
+
+    fn proof_size(&self, receipt: &Receipt) -> usize {
+        bincode::serialized_size(receipt)
+            .map(|n| n as usize)
+            .unwrap_or(0)
+    }
+
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        self.metrics.clone()
+    }
+
+    /// journal 의 모델/입력 해시가 보낸 파일과 같은지 확인한 뒤 출력을 반환
+    fn outputs(&self, receipt: &Receipt) -> Result<Option<Vec<Vec<f64>>>, BackendError> {
+        let spec = self.spec.as_ref().ok_or("setup was not called")?;
+        let outputs = decode_outputs(
+            &receipt.journal,
+            spec,
+            &self.input,
+            self.mode,
+            Guest::Tensor,
+        )?;
+        Ok(Some(outputs))
+    }
+}
@@ -0,0 +1,57 @@
+//! Recursion benchmark for RISC Zero: proves `--count` inference receipts and composes
+//! them into one receipt with the aggregation guest, appending a row to the backend CSV.
+use host::aggregate::AggregateBackend;
+use host::backend::default_workload;
+use host::{flag_value, parse_flag};
+use model_spec::InferenceMode;
+use std::env;
+use zkbench::backend::Workload;
+use zkbench::csv::ResultWriter;
+
+fn usage() -> ! {
+    eprintln!("usage: aggregate [--count N] [--mode float|fixed] [--workload FILE] [--warmup N] [--runs N] [--out FILE] [--dev-mode]");
+    std::process::exit(2);
+}
+
+fn bad_flag(message: String) -> ! {
+    eprintln!("{}", message);
+    usage()
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        usage();
+    }
+    let count: usize = parse_flag(&args, "--count", 2).unwrap_or_else(|e| bad_flag(e));
+    let mode = parse_flag(&args, "--mode", InferenceMode::Fixed).unwrap_or_else(|e| bad_flag(e));
+    let warmup: usize = parse_flag(&args, "--warmup", 0).unwrap_or_else(|e| bad_flag(e));
+    let runs: usize = parse_flag(&args, "--runs", 1).unwrap_or_else(|e| bad_flag(e));
+    let out = flag_value(&args, "--out").unwrap_or("backend_results.csv");
+    if args.iter().any(|a| a == "--dev-mode") {
+        env::set_var("RISC0_DEV_MODE", "1");
+        println!(
+            "RISC0_DEV_MODE=1: receipts are fake, prove time and receipt size are not meaningful"
+        );
+    }
+
+    let mut workload = match flag_value(&args, "--workload") {
+        Some(path) => Workload::load(path).expect("bad workload"),
+        None => default_workload().expect("unable to load model_data workload"),
+    };
+    // 재귀 실험의 exp_num 과 맞추기 위해 영수증 개수를 workload 이름에 붙임
+    workload.name = format!("{}_x{}", workload.name, count);
+
+    let report = zkbench::backend::run(
+        &mut AggregateBackend::new(mode, count),
+        &workload,
+        warmup,
+        runs,
+    )
+    .expect("aggregation failed");
+    report.print();
+    ResultWriter::backend(out, true)
+        .and_then(|mut writer| writer.write_backend(&report))
+        .expect("couldn't write backend CSV");
+    println!("results appended to {}", out);
+}
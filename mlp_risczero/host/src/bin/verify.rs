@@ -0,0 +1,77 @@
+//! Loads a receipt saved by the host, verifies it against `MLP_GUEST_ELF`'s image ID and
+//! prints the decoded journal, timing verification separately from proving.
+use host::parse_flag;
+use host::receipt::{SavedReceipt, RECEIPT_PATH};
+use model_spec::journal::hex;
+use std::env;
+use std::fs;
+use std::time::Instant;
+use zkbench::stats::Summary;
+
+fn usage() -> ! {
+    eprintln!("usage: verify [RECEIPT] [--runs N]");
+    eprintln!(
+        "  RECEIPT    receipt file written by the host (default: {})",
+        RECEIPT_PATH
+    );
+    eprintln!("  --runs N   verify N times and report the mean (default: 1)");
+    std::process::exit(2);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        usage();
+    }
+    let runs: usize = parse_flag(&args, "--runs", 1).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        usage()
+    });
+    // 플래그 값이 아닌 첫 번째 위치 인자를 영수증 경로로 사용
+    let path = args
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(i, a)| !a.starts_with("--") && args[i - 1] != "--runs")
+        .map(|(_, a)| a.as_str())
+        .unwrap_or(RECEIPT_PATH);
+
+    let file_size = fs::metadata(path)
+        .unwrap_or_else(|e| panic!("can't read {}: {}", path, e))
+        .len();
+    let saved = SavedReceipt::load(path).expect("couldn't load receipt");
+    println!(
+        "receipt: {} (mode {}, image {})",
+        path, saved.mode, saved.image_id
+    );
+
+    let mut verify_times = vec![];
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        if let Err(e) = saved.verify() {
+            eprintln!("verification failed: {}", e);
+            std::process::exit(1);
+        }
+        verify_times.push(start.elapsed().as_secs_f64());
+    }
+    let verify = Summary::of(&verify_times).unwrap();
+
+    println!("  verified: ok");
+    println!(
+        "  verify_time: {:.3} (stddev {:.3}, n={})",
+        verify.mean, verify.stddev, verify.n
+    );
+    // proof_size 와 같은 기준 (bincode 로 직렬화한 Receipt), 파일에는 image ID 등이 더해짐
+    println!(
+        "  receipt_size: {}",
+        bincode::serialized_size(&saved.receipt).unwrap_or(0)
+    );
+    println!("  file_size: {}", file_size);
+    let journal = saved.journal().expect("couldn't decode journal");
+    println!("  model_hash: {}", hex(&journal.model_hash));
+    println!("  input_hash: {}", hex(&journal.input_hash));
+    if let Some(scale_bits) = journal.scale_bits {
+        println!("  scale_bits: {}", scale_bits);
+    }
+    println!("  outputs: {:?}", journal.outputs);
+}
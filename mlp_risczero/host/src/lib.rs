@@ -0,0 +1,23 @@
+pub mod aggregate;
+pub mod backend;
+pub mod receipt;
+
+use std::str::FromStr;
+
+// `--name value` 형태의 옵션 값
+pub fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+// 옵션 값을 파싱, 없으면 default. 잘못된 값은 오류 메시지로 돌려주고 호출한 쪽이 usage 를 출력
+pub fn parse_flag<T: FromStr>(args: &[String], name: &str, default: T) -> Result<T, String> {
+    match flag_value(args, name) {
+        None => Ok(default),
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value for {}: {}", name, value)),
+    }
+}
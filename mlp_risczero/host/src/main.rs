@@ -1,49 +1,263 @@
-use methods::MLP_GUEST_ELF;
-use risc0_zkvm::{default_prover, ExecutorEnv};
-use std::fs::File;
-use std::io::Read;
+use host::backend::{
+    default_workload, execute, print_layer_profile, write_layer_profile, Guest, ReceiptKind,
+    Risc0Backend,
+};
+use host::{flag_value, parse_flag};
+use host::receipt::RECEIPT_PATH;
+use model_spec::InferenceMode;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use zkbench::backend::Workload;
+use zkbench::csv::{ResultWriter, SharedBackendRow};
+use zkbench::measure::{peak_rss_kb, run_measured};
 
-fn main() {
-    // JSON 파일에서 모델과 입력 데이터를 읽습니다.
-    let mut model_file =
-        File::open("./model_data/mlp_model_bytes.json").expect("unable to open model file");
-    let mut model_json = String::new();
-    model_file
-        .read_to_string(&mut model_json)
-        .expect("unable to read model data");
-
-    let mut input_file = File::open("./model_data/mlp_input_data_bytes.json")
-        .expect("unable to open input data file");
-    let mut input_json = String::new();
-    input_file
-        .read_to_string(&mut input_json)
-        .expect("unable to read input data");
-
-    // JSON 데이터를 바이트 배열로 변환
-    let model_bytes: Vec<u8> = serde_json::from_str(&model_json).unwrap();
-    let input_bytes: Vec<u8> = serde_json::from_str(&input_json).unwrap();
-
-    // Executor 환경을 설정합니다.
-    let env = ExecutorEnv::builder()
-        .write(&model_bytes)
-        .unwrap()
-        .write(&input_bytes)
-        .unwrap()
-        .build()
-        .unwrap();
-
-    // 기본 prover를 가져옵니다.
-    let prover = default_prover();
-
-    // 지정된 ELF 바이너리를 증명하여 영수증(receipt)을 생성합니다.
-    let receipt = prover.prove(env, MLP_GUEST_ELF).unwrap().receipt;
-
-    // 영수증의 journal에서 출력 값을 추출합니다.
-    let output: Vec<f64> = receipt.journal.decode().unwrap();
-
-    // 출력 결과를 출력합니다. 이 결과는 journal에 커밋된 후 공개된 값입니다.
-    println!(
-        "Hello, world! I generated a proof of guest execution! The model prediction is: {:?}",
-        output
+fn usage() -> ! {
+    eprintln!("usage: host [options]");
+    eprintln!("  --workload FILE        shared workload (default: model_data/mlp_spec.json + mlp_input.json)");
+    eprintln!("  --mode float|fixed|both guest inference path (default: float)");
+    eprintln!(
+        "  --execute              run the executor only and report cycles/segments, no proof"
     );
+    eprintln!("  --guest tensor|smartcore|both  guest for --execute; both compares their cycles (default: tensor)");
+    eprintln!("  --dev-mode             fake receipts (RISC0_DEV_MODE=1) for fast iteration");
+    eprintln!("  --warmup N --runs N    repetitions in prove mode (default: 0, 1)");
+    eprintln!("  --out FILE             benchmark CSV to append to (default: backend_results.csv)");
+    eprintln!("  --shared-out FILE      same results in the shared framework,model,exp_num,... CSV (default: experiment_backends.csv)");
+    eprintln!("  --dump-outputs FILE    proven outputs as JSON for equivcheck");
+    eprintln!("  --receipt FILE         where to save the last receipt (default: receipt.bin)");
+    eprintln!("  --receipt-kind K       composite|succinct|groth16, or all to compare them (default: composite)");
+    eprintln!("  --dump-report FILE     prove/verify time and receipt size as JSON (used by --receipt-kind all)");
+    eprintln!("  --layer-profile FILE   append per-layer cycles, shapes and MACs as CSV");
+    std::process::exit(2);
+}
+
+fn bad_flag(message: String) -> ! {
+    eprintln!("{}", message);
+    usage()
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        usage();
+    }
+    // --mode float|fixed|both: 게스트 추론 방식 (기본 float), both 는 두 방식의 사이클을 비교
+    let modes: Vec<InferenceMode> = match flag_value(&args, "--mode").unwrap_or("float") {
+        "both" => vec![InferenceMode::Float, InferenceMode::Fixed],
+        mode => vec![mode.parse().unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            usage()
+        })],
+    };
+    // dev mode: default_prover() 가 가짜 영수증을 만들고, 같은 프로세스의 verify 도 이를 받아들임
+    if args.iter().any(|a| a == "--dev-mode") {
+        env::set_var("RISC0_DEV_MODE", "1");
+        println!(
+            "RISC0_DEV_MODE=1: receipts are fake, prove time and receipt size are not meaningful"
+        );
+    }
+
+    let workload = match flag_value(&args, "--workload") {
+        Some(path) => Workload::load(path).expect("bad workload"),
+        None => default_workload().expect("unable to load model_data workload"),
+    };
+    let dump_path = flag_value(&args, "--dump-outputs");
+    let profile_path = flag_value(&args, "--layer-profile");
+
+    // --execute: executor 만 실행해 사이클/세그먼트 수 보고
+    if args.iter().any(|a| a == "--execute") {
+        let spec = workload.model().expect("unable to load model spec");
+        // --guest both: 예전 smartcore 게스트와 tensor 게스트의 사이클 비교
+        let guests = match flag_value(&args, "--guest").unwrap_or("tensor") {
+            "both" => vec![Guest::Smartcore, Guest::Tensor],
+            guest => vec![guest.parse().unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                usage()
+            })],
+        };
+        for &mode in &modes {
+            let reports: Vec<_> = guests
+                .iter()
+                .map(|&guest| {
+                    let report =
+                        execute(&spec, &workload.input, mode, guest).expect("executor failed");
+                    report.print();
+                    if let Some(path) = profile_path {
+                        write_layer_profile(path, &workload.name, mode, guest, &report.layers)
+                            .expect("couldn't write layer profile");
+                    }
+                    report
+                })
+                .collect();
+            if let [old, new] = &reports[..] {
+                println!(
+                    "  {} -> {}: total_cycles {} -> {} ({:+.1}%), user_cycles {} -> {} ({:+.1}%)",
+                    old.guest,
+                    new.guest,
+                    old.total_cycles,
+                    new.total_cycles,
+                    change(old.total_cycles, new.total_cycles),
+                    old.user_cycles,
+                    new.user_cycles,
+                    change(old.user_cycles, new.user_cycles)
+                );
+            }
+            if let (Some(path), Some(report)) = (dump_path, reports.last()) {
+                let json = serde_json::json!({ "backend": "risc0", "workload": workload.name, "outputs": report.outputs });
+                std::fs::write(path, serde_json::to_string_pretty(&json).unwrap())
+                    .expect("couldn't write outputs");
+            }
+        }
+        return;
+    }
+
+    // 전체 증명: 공통 ZkBackend 드라이버로 실행하고 벤치마크 CSV 에 한 행씩 추가
+    let warmup: usize = parse_flag(&args, "--warmup", 0).unwrap_or_else(|e| bad_flag(e));
+    let runs: usize = parse_flag(&args, "--runs", 1).unwrap_or_else(|e| bad_flag(e));
+    let out = flag_value(&args, "--out").unwrap_or("backend_results.csv");
+    let shared_out = flag_value(&args, "--shared-out").unwrap_or("experiment_backends.csv");
+    let receipt_path = flag_value(&args, "--receipt").unwrap_or(RECEIPT_PATH);
+    let kind = match flag_value(&args, "--receipt-kind").unwrap_or("composite") {
+        "all" => return compare_receipt_kinds(&args, receipt_path, &modes),
+        kind => kind.parse::<ReceiptKind>().unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            usage()
+        }),
+    };
+    for &mode in &modes {
+        let mut backend = Risc0Backend::new(mode).with_receipt_kind(kind);
+        let mut report = zkbench::backend::run(&mut backend, &workload, warmup, runs)
+            .expect("risc0 backend failed");
+        // 프로세스 전체의 최대 RSS (종류별 비교는 --receipt-kind all 이 종류마다 새 프로세스로 실행)
+        report.metrics.push(("peak_rss_kb", peak_rss_kb() as f64));
+        report.print();
+        print_layer_profile(backend.layers());
+        if let Some(path) = profile_path {
+            write_layer_profile(path, &workload.name, mode, Guest::Tensor, backend.layers())
+                .expect("couldn't write layer profile");
+        }
+        // both 면 모드별로 receipt_float.bin / receipt_fixed.bin 처럼 나눠 저장
+        let path = if modes.len() > 1 {
+            suffixed_path(receipt_path, mode)
+        } else {
+            PathBuf::from(receipt_path)
+        };
+        let size = backend.save_receipt(&path).expect("couldn't save receipt");
+        println!("  receipt saved: {} ({} bytes)", path.display(), size);
+        ResultWriter::backend(out, true)
+            .and_then(|mut writer| writer.write_backend(&report))
+            .expect("couldn't write backend CSV");
+        ResultWriter::shared_backend(shared_out, true)
+            .and_then(|mut writer| writer.write_shared_backend(&SharedBackendRow(&report)))
+            .expect("couldn't write shared CSV");
+        // --dump-outputs <path>: equivcheck 용 출력 JSON (both 면 마지막 모드)
+        if let Some(path) = dump_path {
+            report.write_outputs(path).expect("couldn't write outputs");
+        }
+        // --dump-report <path>: --receipt-kind all 의 비교표가 읽는 요약 (both 면 마지막 모드)
+        if let Some(path) = flag_value(&args, "--dump-report") {
+            let json = serde_json::json!({
+                "prove_time": report.prove.mean,
+                "verify_time": report.verify.mean,
+                "receipt_size": report.proof_size,
+            });
+            fs::write(path, json.to_string()).expect("couldn't write report");
+        }
+    }
+    println!("results appended to {} and {}", out, shared_out);
+}
+
+fn change(old: u64, new: u64) -> f64 {
+    (new as f64 - old as f64) / old as f64 * 100.0
+}
+
+// 같은 실행을 영수증 종류 x 모드마다 별도 프로세스로 증명해, 종류별 최대 메모리를 따로 측정.
+// 증명/검증 시간과 영수증 크기는 자식이 --dump-report 로 남긴 JSON 에서 읽어 한 표로 모음
+fn compare_receipt_kinds(args: &[String], receipt_path: &str, modes: &[InferenceMode]) {
+    let exe = env::current_exe().expect("can't find own executable");
+    // --receipt-kind / --receipt / --mode / --dump-report 와 그 값을 빼고 나머지 옵션은 그대로 넘김
+    let mut rest = vec![];
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        if ["--receipt-kind", "--receipt", "--mode", "--dump-report"].contains(&arg.as_str()) {
+            iter.next();
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+
+    let mut rows = vec![];
+    for kind in ReceiptKind::ALL {
+        for &mode in modes {
+            println!("== receipt kind: {} mode: {}", kind, mode);
+            let mut path = suffixed_path(receipt_path, kind);
+            if modes.len() > 1 {
+                path = suffixed_path(&path.to_string_lossy(), mode);
+            }
+            let report_path =
+                env::temp_dir().join(format!("host_report_{}_{}_{}.json", process::id(), kind, mode));
+            let mut cmd = Command::new(&exe);
+            cmd.args(&rest)
+                .arg("--mode")
+                .arg(mode.to_string())
+                .arg("--receipt-kind")
+                .arg(kind.to_string())
+                .arg("--receipt")
+                .arg(&path)
+                .arg("--dump-report")
+                .arg(&report_path);
+            let measurement = run_measured(&mut cmd).expect("couldn't run prover process");
+            if !measurement.success() {
+                // groth16 은 로컬 Groth16 prover (x86 + Docker) 가 없으면 실패
+                println!(
+                    "{} failed (exit {:?}), skipped",
+                    kind, measurement.exit_code
+                );
+            }
+            let report = fs::read(&report_path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok());
+            let _ = fs::remove_file(&report_path);
+            rows.push((kind, mode, measurement, report));
+        }
+    }
+
+    println!("kind,mode,ok,wall_time,peak_rss_kb,prove_time,verify_time,receipt_size");
+    for (kind, mode, m, report) in rows {
+        // 실패한 자식은 보고서가 없으므로 빈 칸
+        let field = |name: &str| {
+            report
+                .as_ref()
+                .and_then(|r| r.get(name))
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        };
+        println!(
+            "{},{},{},{:.3},{},{},{},{}",
+            kind,
+            mode,
+            m.success(),
+            m.wall.as_secs_f64(),
+            m.max_rss_kb,
+            field("prove_time"),
+            field("verify_time"),
+            field("receipt_size")
+        );
+    }
+}
+
+// receipt.bin -> receipt_fixed.bin 처럼 확장자 앞에 접미사를 붙임
+fn suffixed_path(path: &str, suffix: impl std::fmt::Display) -> PathBuf {
+    let path = Path::new(path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("receipt");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, suffix, ext),
+        None => format!("{}_{}", stem, suffix),
+    };
+    path.with_file_name(name)
 }
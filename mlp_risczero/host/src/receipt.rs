@@ -0,0 +1,76 @@
+//! Receipts saved to disk together with the image ID and guest mode they were proven with,
+//! so `verify` can check and decode them without rerunning the prover.
+use methods::MLP_GUEST_ID;
+use model_spec::journal::InferenceJournal;
+use model_spec::InferenceMode;
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use zkbench::backend::BackendError;
+
+pub const RECEIPT_PATH: &str = "./receipt.bin";
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedReceipt {
+    /// Image ID of the guest that produced the receipt.
+    pub image_id: Digest,
+    pub mode: InferenceMode,
+    pub receipt: Receipt,
+}
+
+impl SavedReceipt {
+    pub fn new(receipt: Receipt, mode: InferenceMode) -> Self {
+        SavedReceipt {
+            image_id: Digest::from(MLP_GUEST_ID),
+            mode,
+            receipt,
+        }
+    }
+
+    /// Writes the bincode encoding and returns its size in bytes.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<usize, BackendError> {
+        let bytes = bincode::serialize(self)?;
+        fs::write(path.as_ref(), &bytes)
+            .map_err(|e| format!("can't write {}: {}", path.as_ref().display(), e))?;
+        Ok(bytes.len())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| format!("can't read {}: {}", path.as_ref().display(), e))?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    /// Checks the receipt against the guest this binary was built with, not the image ID
+    /// stored in the file; a mismatch means the guest changed since the receipt was made.
+    pub fn verify(&self) -> Result<(), BackendError> {
+        let expected = Digest::from(MLP_GUEST_ID);
+        if self.image_id != expected {
+            return Err(format!(
+                "receipt was proven for image {}, this build's guest is {}",
+                self.image_id, expected
+            )
+            .into());
+        }
+        self.receipt.verify(MLP_GUEST_ID)?;
+        Ok(())
+    }
+
+    /// Decoded journal, with fixed-point outputs dequantized by the committed scale.
+    pub fn journal(&self) -> Result<InferenceJournal<f64>, BackendError> {
+        Ok(match self.mode {
+            InferenceMode::Float => self.receipt.journal.decode()?,
+            InferenceMode::Fixed => {
+                let fixed: InferenceJournal<i64> = self.receipt.journal.decode()?;
+                InferenceJournal {
+                    model_hash: fixed.model_hash,
+                    input_hash: fixed.input_hash,
+                    scale_bits: fixed.scale_bits,
+                    outputs: fixed.dequantize(),
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use model_spec::journal::{Hash, InferenceJournal};
+use model_spec::InferenceMode;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::serde::from_slice;
+
+// 모든 영수증이 같은 모델에서 나왔는지 확인하고 그 해시와 출력들을 반환
+fn decode<T: serde::de::DeserializeOwned>(journals: &[Vec<u8>]) -> (Hash, Vec<Vec<Vec<T>>>) {
+    let journals: Vec<InferenceJournal<T>> =
+        journals.iter().map(|j| from_slice(j).unwrap()).collect();
+    let model_hash = journals.first().map(|j| j.model_hash).unwrap_or_default();
+    assert!(
+        journals.iter().all(|j| j.model_hash == model_hash),
+        "receipts were made with different models"
+    );
+    (
+        model_hash,
+        journals.into_iter().map(|j| j.outputs).collect(),
+    )
+}
+
+// 같은 모양의 출력 행렬들을 원소별로 더함
+fn sum_outputs<T: Copy + std::ops::Add<Output = T>>(outputs: Vec<Vec<Vec<T>>>) -> Vec<Vec<T>> {
+    let mut outputs = outputs.into_iter();
+    let mut sum = outputs.next().unwrap_or_default();
+    for output in outputs {
+        assert_eq!(
+            output.len(),
+            sum.len(),
+            "receipts have different batch sizes"
+        );
+        for (acc, row) in sum.iter_mut().zip(output) {
+            assert_eq!(
+                row.len(),
+                acc.len(),
+                "receipts have different output widths"
+            );
+            for (a, y) in acc.iter_mut().zip(row) {
+                *a = *a + y;
+            }
+        }
+    }
+    sum
+}
+
+fn main() {
+    // Host로부터 추론 게스트의 image ID, 추론 방식, 각 영수증의 journal 을 읽습니다.
+    let image_id: [u32; 8] = env::read();
+    let mode: InferenceMode = env::read();
+    let journals: Vec<Vec<u8>> = env::read();
+
+    // 각 journal 에 대해 추론 영수증이 존재함을 검증 (host 가 add_assumption 으로 넘긴 영수증으로 해소됨)
+    for journal in &journals {
+        env::verify(image_id, journal).unwrap();
+    }
+
+    // 검증한 image ID, 모델 해시, 영수증 개수, 출력의 원소별 합을 journal 에 커밋
+    let count = journals.len() as u32;
+    match mode {
+        InferenceMode::Float => {
+            let (model_hash, outputs) = decode::<f64>(&journals);
+            env::commit(&(image_id, model_hash, count, sum_outputs(outputs)));
+        }
+        InferenceMode::Fixed => {
+            let (model_hash, outputs) = decode::<i64>(&journals);
+            env::commit(&(image_id, model_hash, count, sum_outputs(outputs)));
+        }
+    }
+}
@@ -1,71 +1,131 @@
-use ndarray::array;
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use model_spec::binary;
+use model_spec::journal::{CycleReport, Hash, InferenceJournal};
+use model_spec::tensor::Layer;
+use model_spec::{FloatModel, InferenceMode, QuantizedModel};
 use risc0_zkvm::guest::env;
-use smartcore::linalg::basic::arrays::{Array, Array2, MutArray};
-use smartcore::linalg::basic::matrix::DenseMatrix;
-use smartcore::linear::linear_regression::LinearRegression;
-use std::fs::File;
-use std::io::Write;
+use risc0_zkvm::sha::{Impl, Sha256};
 
-// 활성화 함수 (ReLU)
-fn relu(x: f64) -> f64 {
-    if x > 0.0 {
-        x
-    } else {
-        0.0
-    }
-}
+risc0_zkvm::guest::entry!(main);
 
-// 퍼셉트론 함수: 선형 변환 + 활성화 함수
-fn perceptron(
-    input: &DenseMatrix<f64>,
-    weights: &DenseMatrix<f64>,
-    biases: &Vec<f64>,
-) -> DenseMatrix<f64> {
-    let mut output = DenseMatrix::from_2d_vec(&vec![vec![0.0; biases.len()]; input.shape().0]);
+// host 가 보낸 바이너리 파일 (바이트 길이 + 원본 바이트)을 그대로 SHA-256 (가속기 사용).
+// 헤더/CRC 검사와 디코딩은 호출하는 쪽이 함
+fn read_file() -> (Vec<u8>, Hash) {
+    let len: u32 = env::read();
+    let mut bytes = vec![0u8; len as usize];
+    env::read_slice(&mut bytes);
+    let hash = Impl::hash_bytes(&bytes).as_bytes().try_into().unwrap();
+    (bytes, hash)
+}
 
-    for (i, row) in input.row_iter().enumerate() {
-        let row_vec: Vec<f64> = row.iterator(0).map(|&x| x).collect(); // 슬라이스 대신 Vec<f64>로 변환
-        for j in 0..biases.len() {
-            // 선형 변환: 가중치 * 입력 + 편향
-            let mut sum = biases[j];
-            for (k, &input_val) in row_vec.iter().enumerate() {
-                sum += input_val * weights.get((k, j));
-            }
-            // 활성화 함수 (ReLU 적용)
-            output.set((i, j), relu(sum));
-        }
+// 샘플 스트림: host 는 f64 입력 파일(`mlp_input.bin` 과 같은 바이트)을 보냄. 행을 하나씩 디코딩해
+// 바로 추론하고 샘플당 사이클을 기록하며, 입력 해시는 파일 바이트의 SHA-256 이라 `sha256sum` 으로 재현 가능.
+// 행 너비가 모델의 input_size 와 다르면 추론 전에 중단
+fn stream_samples<U>(
+    input_size: usize,
+    mut infer: impl FnMut(&[f64]) -> Vec<U>,
+) -> (Vec<Vec<U>>, Hash, Vec<u64>) {
+    let (bytes, input_hash) = read_file();
+    let (header, rows) = binary::input_rows(&bytes).expect("bad input file");
+    assert!(
+        header.width as usize == input_size,
+        "input rows have {} values, model expects {}",
+        header.width,
+        input_size
+    );
+    let mut outputs = Vec::with_capacity(header.count as usize);
+    let mut cycles = Vec::with_capacity(header.count as usize);
+    for row in rows {
+        let start = env::cycle_count();
+        outputs.push(infer(&row));
+        cycles.push(env::cycle_count() - start);
     }
+    (outputs, input_hash, cycles)
+}
 
-    output
+// 층마다 forward 앞뒤로 env::cycle_count() 를 읽어 그 층의 사이클을 layer_cycles 에 누적
+fn forward_profiled<T: Clone>(
+    layers: &[Layer<T>],
+    input: &[T],
+    layer_cycles: &mut [u64],
+    forward: impl Fn(&Layer<T>, &[T]) -> Vec<T>,
+) -> Vec<T> {
+    let mut x = input.to_vec();
+    for (layer, cycles) in layers.iter().zip(layer_cycles.iter_mut()) {
+        let start = env::cycle_count();
+        x = forward(layer, &x);
+        *cycles += env::cycle_count() - start;
+    }
+    x
 }
 
-// MLP 구조에서 forward propagation을 수행
-fn mlp_forward(
-    input: DenseMatrix<f64>,
-    layers: &[(DenseMatrix<f64>, Vec<f64>)],
-) -> DenseMatrix<f64> {
-    let mut output = input;
+// f64 경로: 평탄한 행 우선 행렬로 forward (zkVM에서 부동소수점은 소프트 에뮬레이션)
+fn run_float(start: u64) -> CycleReport {
+    let (bytes, model_hash) = read_file();
+    let model = FloatModel::from_bytes(&bytes).expect("bad model file");
+    let load_cycles = env::cycle_count() - start;
 
-    for (weights, biases) in layers.iter() {
-        output = perceptron(&output, weights, biases);
-    }
+    let mut layer_cycles = vec![0; model.layers.len()];
+    let (outputs, input_hash, sample_cycles) = stream_samples(model.input_size, |x| {
+        forward_profiled(&model.layers, x, &mut layer_cycles, |layer, x| {
+            layer.forward(x)
+        })
+    });
 
-    output
+    // 모델/입력 해시와 예측 결과(샘플별 행)를 journal에 커밋하여 공개합니다.
+    env::commit(&InferenceJournal {
+        model_hash,
+        input_hash,
+        scale_bits: None,
+        outputs,
+    });
+    CycleReport {
+        load_cycles,
+        sample_cycles,
+        layer_cycles,
+    }
 }
 
-fn main() {
-    // Host로부터 직렬화된 입력 데이터를 읽습니다.
-    let model_data_bytes: Vec<u8> = env::read();
-    let input_data_bytes: Vec<u8> = env::read();
-
-    // 역직렬화하여 MLPInput 구조체로 변환합니다.
-    let layers: Vec<(DenseMatrix<f64>, Vec<f64>)> =
-        rmp_serde::from_slice(&model_data_bytes).unwrap();
-    let x: DenseMatrix<f64> = rmp_serde::from_slice(&input_data_bytes).unwrap();
+// 고정소수점 경로: host가 양자화한 가중치로 정수 연산만 수행. 입력은 f64 파일로 받아
+// host 와 같은 반올림(model_spec::quantize)으로 샘플마다 양자화함
+fn run_fixed(start: u64) -> CycleReport {
+    let (bytes, model_hash) = read_file();
+    let model = QuantizedModel::from_bytes(&bytes).expect("bad model file");
+    let load_cycles = env::cycle_count() - start;
 
-    // MLP 예측 수행
-    let prediction = mlp_forward(x.clone(), &layers);
+    let mut layer_cycles = vec![0; model.layers.len()];
+    let (outputs, input_hash, sample_cycles) = stream_samples(model.input_size, |x| {
+        let x: Vec<i64> = x.iter().map(|&v| model.quantize(v)).collect();
+        forward_profiled(&model.layers, &x, &mut layer_cycles, |layer, x| {
+            layer.forward(x, model.scale_bits)
+        })
+    });
+    env::commit(&InferenceJournal {
+        model_hash,
+        input_hash,
+        scale_bits: Some(model.scale_bits),
+        outputs,
+    });
+    CycleReport {
+        load_cycles,
+        sample_cycles,
+        layer_cycles,
+    }
+}
 
-    // 예측 결과를 journal에 커밋하여 공개합니다.
-    env::commit(&prediction);
+fn main() {
+    let start = env::cycle_count();
+    let mode: InferenceMode = env::read();
+    let report = match mode {
+        InferenceMode::Float => run_float(start),
+        InferenceMode::Fixed => run_fixed(start),
+    };
+    // 사이클 수는 증명 대상이 아니므로 journal 대신 stdout 으로 host 에 전달
+    env::write(&report);
 }
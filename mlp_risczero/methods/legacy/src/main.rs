@@ -0,0 +1,178 @@
+// smartcore DenseMatrix 기반의 예전 게스트. tensor 게스트(guest/)와 사이클을 비교하기 위해 남겨 둠
+// (host --execute --guest both). float 경로는 ModelSpec 을 그대로 받음
+use model_spec::journal::{CycleReport, Hash, InferenceJournal};
+use model_spec::{Activation, InferenceMode, ModelSpec, QuantizedModel};
+use ndarray::array;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::serde::from_slice;
+use risc0_zkvm::sha::{Impl, Sha256};
+use serde::de::DeserializeOwned;
+use smartcore::linalg::basic::arrays::{Array, Array2, MutArray};
+use smartcore::linalg::basic::matrix::DenseMatrix;
+use smartcore::linear::linear_regression::LinearRegression;
+use std::fs::File;
+use std::io::Write;
+
+// 활성화 함수 (ReLU)
+fn relu(x: f64) -> f64 {
+    if x > 0.0 {
+        x
+    } else {
+        0.0
+    }
+}
+
+// 퍼셉트론 함수: 선형 변환 + 활성화 함수
+fn perceptron(
+    input: &DenseMatrix<f64>,
+    weights: &DenseMatrix<f64>,
+    biases: &Vec<f64>,
+    activation: Activation,
+) -> DenseMatrix<f64> {
+    let mut output = DenseMatrix::from_2d_vec(&vec![vec![0.0; biases.len()]; input.shape().0]);
+
+    for (i, row) in input.row_iter().enumerate() {
+        let row_vec: Vec<f64> = row.iterator(0).map(|&x| x).collect(); // 슬라이스 대신 Vec<f64>로 변환
+        for j in 0..biases.len() {
+            // 선형 변환: 가중치 * 입력 + 편향
+            let mut sum = biases[j];
+            for (k, &input_val) in row_vec.iter().enumerate() {
+                sum += input_val * weights.get((k, j));
+            }
+            // 활성화 함수 (스펙에 따라 ReLU 또는 그대로)
+            let value = match activation {
+                Activation::Relu => relu(sum),
+                Activation::Linear => sum,
+            };
+            output.set((i, j), value);
+        }
+    }
+
+    output
+}
+
+// MLP 구조에서 forward propagation을 수행. perceptron 호출마다 사이클을 재어 layer_cycles 에 누적
+fn mlp_forward(
+    input: DenseMatrix<f64>,
+    layers: &[(DenseMatrix<f64>, Vec<f64>, Activation)],
+    layer_cycles: &mut [u64],
+) -> DenseMatrix<f64> {
+    let mut output = input;
+
+    for ((weights, biases, activation), cycles) in layers.iter().zip(layer_cycles.iter_mut()) {
+        let start = env::cycle_count();
+        output = perceptron(&output, weights, biases, *activation);
+        *cycles += env::cycle_count() - start;
+    }
+
+    output
+}
+
+// host 가 직렬화해 보낸 워드를 그대로 SHA-256 (가속기 사용) 한 뒤 역직렬화
+fn read_hashed<T: DeserializeOwned>() -> (T, Hash) {
+    let words: Vec<u32> = env::read();
+    let hash = Impl::hash_words(&words).as_bytes().try_into().unwrap();
+    (from_slice(&words).unwrap(), hash)
+}
+
+// 샘플 스트림: 개수 다음에 샘플별 워드 벡터가 옴. 읽는 즉시 추론하고 샘플당 사이클을 기록하며,
+// 입력 해시는 샘플 워드를 이어 붙인 것의 SHA-256
+fn stream_samples<T: DeserializeOwned, U>(
+    mut infer: impl FnMut(Vec<T>) -> Vec<U>,
+) -> (Vec<Vec<U>>, Hash, Vec<u64>) {
+    let count: u32 = env::read();
+    let mut seen: Vec<u32> = vec![];
+    let mut outputs = Vec::with_capacity(count as usize);
+    let mut cycles = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let start = env::cycle_count();
+        let words: Vec<u32> = env::read();
+        outputs.push(infer(from_slice(&words).unwrap()));
+        seen.extend_from_slice(&words);
+        cycles.push(env::cycle_count() - start);
+    }
+    let input_hash = Impl::hash_words(&seen).as_bytes().try_into().unwrap();
+    (outputs, input_hash, cycles)
+}
+
+// f64 경로: smartcore 행렬로 변환해 forward (zkVM에서 부동소수점은 소프트 에뮬레이션)
+fn run_float(start: u64) -> CycleReport {
+    // Host로부터 모델 스펙을 한 번만 읽어 smartcore 행렬로 변환 (weights[k][j] = 입력 k -> 출력 j)
+    let (spec, model_hash): (ModelSpec, Hash) = read_hashed();
+    spec.validate().unwrap();
+    let layers: Vec<(DenseMatrix<f64>, Vec<f64>, Activation)> = spec
+        .layers
+        .iter()
+        .map(|layer| {
+            (
+                DenseMatrix::from_2d_vec(&layer.weights),
+                layer.bias.clone(),
+                layer.activation,
+            )
+        })
+        .collect();
+    let load_cycles = env::cycle_count() - start;
+
+    // 샘플마다 1행 행렬로 MLP 예측 수행
+    let mut layer_cycles = vec![0; layers.len()];
+    let (outputs, input_hash, sample_cycles) = stream_samples(|x: Vec<f64>| {
+        let prediction = mlp_forward(
+            DenseMatrix::from_2d_vec(&vec![x]),
+            &layers,
+            &mut layer_cycles,
+        );
+        prediction.iterator(0).copied().collect()
+    });
+
+    // 모델/입력 해시와 예측 결과(샘플별 행)를 journal에 커밋하여 공개합니다.
+    env::commit(&InferenceJournal {
+        model_hash,
+        input_hash,
+        scale_bits: None,
+        outputs,
+    });
+    CycleReport {
+        load_cycles,
+        sample_cycles,
+        layer_cycles,
+    }
+}
+
+// 고정소수점 경로: host가 양자화한 가중치/입력으로 정수 연산만 수행
+fn run_fixed(start: u64) -> CycleReport {
+    let (model, model_hash): (QuantizedModel, Hash) = read_hashed();
+    let load_cycles = env::cycle_count() - start;
+
+    // 고정소수점 경로는 tensor 게스트와 같은 Layer::forward 를 씀
+    let mut layer_cycles = vec![0; model.layers.len()];
+    let (outputs, input_hash, sample_cycles) = stream_samples(|mut x: Vec<i64>| {
+        for (layer, cycles) in model.layers.iter().zip(layer_cycles.iter_mut()) {
+            let start = env::cycle_count();
+            x = layer.forward(&x, model.scale_bits);
+            *cycles += env::cycle_count() - start;
+        }
+        x
+    });
+    env::commit(&InferenceJournal {
+        model_hash,
+        input_hash,
+        scale_bits: Some(model.scale_bits),
+        outputs,
+    });
+    CycleReport {
+        load_cycles,
+        sample_cycles,
+        layer_cycles,
+    }
+}
+
+fn main() {
+    let start = env::cycle_count();
+    let mode: InferenceMode = env::read();
+    let report = match mode {
+        InferenceMode::Float => run_float(start),
+        InferenceMode::Fixed => run_fixed(start),
+    };
+    // 사이클 수는 증명 대상이 아니므로 journal 대신 stdout 으로 host 에 전달
+    env::write(&report);
+}
@@ -1,100 +1,540 @@
+use model_spec::{
+    binary, Activation, Dense, ModelSpec, Quantization, DEFAULT_SCALE_BITS, SPEC_VERSION,
+};
 use ndarray::array;
-use smartcore::linalg::basic::arrays::{Array, Array2, MutArray};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use smartcore::linalg::basic::arrays::{Array, Array2};
 use smartcore::linalg::basic::matrix::DenseMatrix;
 use smartcore::linear::linear_regression::LinearRegression;
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
 use std::io::Write;
+use std::path::Path;
+use train::{Dataset, Optimizer, TrainConfig};
 
-// 활성화 함수 (ReLU)
-fn relu(x: f64) -> f64 {
-    if x > 0.0 {
-        x
-    } else {
-        0.0
+mod train;
+
+// 예전 고정 모델: 네 점에 LinearRegression 을 맞춘 첫 층 + 0.3 가중치의 둘째 층
+fn legacy_model(scale_bits: u32) {
+    // 입력 데이터 준비 (여기서는 간단히 2차원 데이터를 사용)
+    let x = DenseMatrix::from_2d_array(&[&[1.0, 1.0], &[1.0, 2.0], &[2.0, 2.0], &[2.0, 3.0]]);
+
+    // 레이블 (목표값)
+    let y = array![6.0, 8.0, 9.0, 11.0];
+
+    // 첫 번째 층: 선형 회귀 모델 사용
+    let model1 = LinearRegression::fit(&x, &y.to_vec(), Default::default()).unwrap();
+    let coefficients = model1.coefficients(); // coefficients는 (입력 수 x 1) DenseMatrix입니다.
+    let layer1 = Dense {
+        outputs: 1,
+        activation: Activation::Relu,
+        weights: (0..coefficients.shape().0)
+            .map(|k| vec![*coefficients.get((k, 0))])
+            .collect(),
+        bias: vec![*model1.intercept()], // intercept는 단일 f64 값입니다.
+    };
+
+    // 두 번째 층: 수동으로 가중치 설정
+    // (첫 층 출력이 1개라 예전 2x1 가중치에서도 첫 행 0.3만 쓰였음)
+    let layer2 = Dense {
+        outputs: 1,
+        activation: Activation::Relu,
+        weights: vec![vec![0.3]],
+        bias: vec![0.0],
+    };
+
+    // 다층 퍼셉트론 (MLP) 구조를 모델 스펙으로 구성
+    let spec = ModelSpec {
+        version: SPEC_VERSION,
+        name: "mlp_2_1_1".to_string(),
+        input_size: x.shape().1,
+        quantization: Some(Quantization { scale_bits }),
+        layers: vec![layer1, layer2],
+    };
+    spec.validate().expect("generated spec is inconsistent");
+
+    // 게스트와 같은 평탄한 텐서 경로로 MLP 예측 수행
+    let model = spec.float_model();
+    for (i, row) in x.row_iter().enumerate() {
+        let row: Vec<f64> = row.iterator(0).copied().collect();
+        println!("Prediction for input {}: {:?}", i, model.forward(&row));
+    }
+
+    // 게스트의 고정소수점 경로와 같은 양자화로 예측해 오차를 확인
+    for (i, row) in x.row_iter().enumerate() {
+        let row: Vec<f64> = row.iterator(0).copied().collect();
+        let quantized: Vec<i64> = row.iter().map(|&v| spec.quantize(v)).collect();
+        let fixed: Vec<f64> = spec
+            .forward_fixed(&quantized)
+            .into_iter()
+            .map(|y| spec.dequantize(y))
+            .collect();
+        println!(
+            "Fixed-point prediction for input {} (scale 2^{}): {:?}",
+            i, scale_bits, fixed
+        );
+    }
+    spec.save("model_data/mlp_spec.json")
+        .expect("Unable to write model spec");
+    spec.save_binary("model_data/mlp_spec.bin")
+        .expect("Unable to write binary model");
+
+    let input: Vec<Vec<f64>> = x
+        .row_iter()
+        .map(|row| row.iterator(0).copied().collect())
+        .collect();
+    let mut f = File::create("model_data/mlp_input.json").expect("unable to create file");
+    f.write_all(serde_json::to_string(&input).unwrap().as_bytes())
+        .expect("Unable to write data");
+    binary::save_input("model_data/mlp_input.bin", &input).expect("Unable to write binary input");
+}
+
+/// Weight initialization for sweep models.
+#[derive(Clone, Copy, PartialEq)]
+enum Init {
+    /// All weights and biases zero, like the kimchi circuit and the ezkl notebook.
+    Zeros,
+    /// Seeded uniform Xavier weights, small biases.
+    Random,
+    /// Random hidden layers, then a linear output layer fit by least squares to `sum(x)`.
+    Trained,
+}
+
+struct SweepArgs {
+    width: usize,
+    depth: Option<usize>,
+    outputs: usize,
+    init: Init,
+    seed: u64,
+    samples: usize,
+    scale_bits: u32,
+}
+
+fn random_dense(rng: &mut StdRng, inputs: usize, outputs: usize, activation: Activation) -> Dense {
+    let limit = (6.0 / (inputs + outputs) as f64).sqrt();
+    Dense {
+        outputs,
+        activation,
+        weights: (0..inputs)
+            .map(|_| (0..outputs).map(|_| rng.gen_range(-limit..limit)).collect())
+            .collect(),
+        bias: (0..outputs).map(|_| rng.gen_range(0.0..0.1)).collect(),
     }
 }
 
-// 퍼셉트론 함수: 선형 변환 + 활성화 함수
-fn perceptron(
-    input: &DenseMatrix<f64>,
-    weights: &DenseMatrix<f64>,
-    biases: &Vec<f64>,
-) -> DenseMatrix<f64> {
-    let mut output = DenseMatrix::from_2d_vec(&vec![vec![0.0; biases.len()]; input.shape().0]);
-
-    for (i, row) in input.row_iter().enumerate() {
-        let row_vec: Vec<f64> = row.iterator(0).map(|&x| x).collect(); // 슬라이스 대신 Vec<f64>로 변환
-        for j in 0..biases.len() {
-            // 선형 변환: 가중치 * 입력 + 편향
-            let mut sum = biases[j];
-            for (k, &input_val) in row_vec.iter().enumerate() {
-                sum += input_val * weights.get((k, j));
+fn zero_dense(inputs: usize, outputs: usize, activation: Activation) -> Dense {
+    Dense {
+        outputs,
+        activation,
+        weights: vec![vec![0.0; outputs]; inputs],
+        bias: vec![0.0; outputs],
+    }
+}
+
+// (HᵀH + λI) w = Hᵀy 를 가우스 소거로 풂 (열이 수십 개 이하라 충분)
+fn ridge_fit(features: &[Vec<f64>], targets: &[f64], lambda: f64) -> Vec<f64> {
+    let n = features[0].len();
+    let mut a = vec![vec![0.0; n + 1]; n];
+    for (row, &y) in features.iter().zip(targets) {
+        for i in 0..n {
+            for j in 0..n {
+                a[i][j] += row[i] * row[j];
             }
-            // 활성화 함수 (ReLU 적용)
-            output.set((i, j), relu(sum));
+            a[i][n] += row[i] * y;
         }
     }
-
-    output
+    for (i, row) in a.iter_mut().enumerate() {
+        row[i] += lambda;
+    }
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))
+            .unwrap();
+        a.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in a.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * p;
+            }
+        }
+    }
+    let mut w = vec![0.0; n];
+    for i in (0..n).rev() {
+        let rest: f64 = (i + 1..n).map(|j| a[i][j] * w[j]).sum();
+        w[i] = (a[i][n] - rest) / a[i][i];
+    }
+    w
 }
 
-// MLP 구조에서 forward propagation을 수행
-fn mlp_forward(
-    input: DenseMatrix<f64>,
-    layers: &[(DenseMatrix<f64>, Vec<f64>)],
-) -> DenseMatrix<f64> {
-    let mut output = input;
+// exp 번 실험 모델: kimchi 와 같은 depth = 2^exp 개의 width x width ReLU 층 (+ 선택적 선형 출력층)
+fn sweep_model(exp: u32, args: &SweepArgs) {
+    let depth = args.depth.unwrap_or(1 << exp);
+    let width = args.width;
+    let outputs = match (args.init, args.outputs) {
+        (Init::Trained, 0) => 1,
+        (_, outputs) => outputs,
+    };
+    // 실험마다 다른 가중치가 나오도록 seed 에 exp 를 섞음
+    let mut rng = StdRng::seed_from_u64(args.seed ^ exp as u64);
 
-    for (weights, biases) in layers.iter() {
-        output = perceptron(&output, weights, biases);
+    let mut layers: Vec<Dense> = (0..depth)
+        .map(|_| match args.init {
+            Init::Zeros => zero_dense(width, width, Activation::Relu),
+            Init::Random | Init::Trained => random_dense(&mut rng, width, width, Activation::Relu),
+        })
+        .collect();
+    let mut spec = ModelSpec {
+        version: SPEC_VERSION,
+        name: format!("mlp{}", exp),
+        input_size: width,
+        quantization: Some(Quantization {
+            scale_bits: args.scale_bits,
+        }),
+        layers: layers.clone(),
+    };
+
+    if outputs > 0 {
+        let output = match args.init {
+            Init::Zeros => zero_dense(width, outputs, Activation::Linear),
+            Init::Random => random_dense(&mut rng, width, outputs, Activation::Linear),
+            Init::Trained => {
+                // 은닉층 출력을 특징으로 sum(x) 를 맞추는 출력층을 최소제곱으로 학습
+                let train: Vec<Vec<f64>> = (0..256)
+                    .map(|_| (0..width).map(|_| rng.gen_range(0.0..1.0)).collect())
+                    .collect();
+                let hidden = spec.float_model();
+                let features: Vec<Vec<f64>> = train
+                    .iter()
+                    .map(|x| {
+                        let mut h = hidden.forward(x);
+                        h.push(1.0);
+                        h
+                    })
+                    .collect();
+                let targets: Vec<f64> = train.iter().map(|x| x.iter().sum()).collect();
+                let w = ridge_fit(&features, &targets, 1e-3);
+                let mse = features
+                    .iter()
+                    .zip(&targets)
+                    .map(|(h, y)| (h.iter().zip(&w).map(|(a, b)| a * b).sum::<f64>() - y).powi(2))
+                    .sum::<f64>()
+                    / targets.len() as f64;
+                println!(
+                    "mlp{}: output layer fit to sum(x), train mse {:.6}",
+                    exp, mse
+                );
+                Dense {
+                    outputs,
+                    activation: Activation::Linear,
+                    weights: w[..width].iter().map(|&v| vec![v; outputs]).collect(),
+                    bias: vec![w[width]; outputs],
+                }
+            }
+        };
+        layers.push(output);
+        spec.layers = layers;
     }
+    spec.validate().expect("generated spec is inconsistent");
+
+    let input: Vec<Vec<f64>> = (0..args.samples)
+        .map(|_| (0..width).map(|_| rng.gen_range(0.0..1.0)).collect())
+        .collect();
 
-    output
+    // model_data/mlp{exp}/ 에 스펙과 입력 (JSON + 바이너리), 공통 workload 파일을 씀.
+    // workload 는 바이너리 파일을 가리킴
+    let dir = Path::new("model_data").join(format!("mlp{}", exp));
+    fs::create_dir_all(&dir).expect("unable to create experiment directory");
+    spec.save(dir.join("mlp_spec.json"))
+        .expect("Unable to write model spec");
+    fs::write(
+        dir.join("mlp_input.json"),
+        serde_json::to_string(&input).unwrap(),
+    )
+    .expect("Unable to write data");
+    spec.save_binary(dir.join("mlp_spec.bin"))
+        .expect("Unable to write binary model");
+    binary::save_input(dir.join("mlp_input.bin"), &input).expect("Unable to write binary input");
+    let workload = serde_json::json!({
+        "name": spec.name,
+        "layers": spec.layer_sizes(),
+        "input_file": "mlp_input.bin",
+        "spec": "mlp_spec.bin",
+    });
+    fs::write(
+        dir.join("workload.json"),
+        serde_json::to_string_pretty(&workload).unwrap(),
+    )
+    .expect("Unable to write workload");
+
+    let model = spec.float_model();
+    let prediction: Vec<Vec<f64>> = input.iter().map(|x| model.forward(x)).collect();
+    println!(
+        "mlp{}: depth {} width {} outputs {} -> {} (predictions {:?})",
+        exp,
+        depth,
+        width,
+        outputs,
+        dir.display(),
+        prediction
+    );
 }
 
-fn main() {
-    // 입력 데이터 준비 (여기서는 간단히 2차원 데이터를 사용)
-    let x = DenseMatrix::from_2d_array(&[&[1.0, 1.0], &[1.0, 2.0], &[2.0, 2.0], &[2.0, 3.0]]);
+struct TrainArgs {
+    /// Number of trailing CSV columns used as targets.
+    targets: usize,
+    config: TrainConfig,
+    test_fraction: f64,
+    seed: u64,
+    samples: usize,
+    scale_bits: u32,
+}
 
-    // 레이블 (목표값)
-    let y = array![6.0, 8.0, 9.0, 11.0];
+// CSV 데이터셋으로 MLP 를 학습해 model_data/trained_<이름>/ 에 스펙, 입력, workload 파일을 씀
+fn trained_model(csv: &Path, args: &TrainArgs) {
+    let data = Dataset::load_csv(csv, args.targets).unwrap_or_else(|e| panic!("{}", e));
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let (train_set, test_set) = data
+        .split(args.test_fraction, &mut rng)
+        .unwrap_or_else(|e| panic!("{}", e));
+    let stem = csv
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("dataset");
+    println!(
+        "{}: {} train / {} test rows, {} features -> {} targets, hidden {:?}, {:?}",
+        stem,
+        train_set.len(),
+        test_set.len(),
+        data.columns.len() - args.targets,
+        args.targets,
+        args.config.hidden,
+        args.config.optimizer
+    );
 
-    // 첫 번째 층: 선형 회귀 모델 사용
-    let model1 = LinearRegression::fit(&x, &y.to_vec(), Default::default()).unwrap();
-    let layer1_weights = model1.coefficients().clone(); // coefficients는 DenseMatrix입니다.
-    let layer1_biases = vec![model1.intercept().to_owned()]; // intercept는 단일 f64 값입니다.
+    let report_every = (args.config.epochs / 10).max(1);
+    let model = train::train(&train_set, &args.config, &mut rng, |epoch, loss| {
+        if epoch % report_every == 0 || epoch == 1 {
+            println!(
+                "  epoch {:>5}: train loss (standardized) {:.5}",
+                epoch, loss
+            );
+        }
+    })
+    .unwrap_or_else(|e| panic!("{}", e));
 
-    // 두 번째 층: 수동으로 가중치 설정
-    let layer2_weights = DenseMatrix::from_2d_array(&[&[0.3], &[0.7]]);
-    let layer2_biases = vec![0.0];
+    let spec = ModelSpec {
+        version: SPEC_VERSION,
+        name: format!(
+            "mlp_{}_{}",
+            stem,
+            std::iter::once(model.input_size)
+                .chain(model.layers.iter().map(|l| l.outputs()))
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join("_")
+        ),
+        input_size: model.input_size,
+        quantization: Some(Quantization {
+            scale_bits: args.scale_bits,
+        }),
+        layers: model
+            .layers
+            .iter()
+            .map(|l| Dense {
+                outputs: l.outputs(),
+                activation: l.activation,
+                weights: l.weights.to_rows(),
+                bias: l.bias.clone(),
+            })
+            .collect(),
+    };
+    spec.validate().expect("trained spec is inconsistent");
 
-    // 다층 퍼셉트론 (MLP) 구조 설정
-    let layers: Vec<(DenseMatrix<f64>, Vec<f64>)> = vec![
-        (layer1_weights, layer1_biases),
-        (layer2_weights, layer2_biases),
-    ];
+    // 학습된 가중치를 고정소수점으로 돌렸을 때의 오차 (게스트/kimchi 의 고정소수점 경로와 같음)
+    let fixed = |x: &[f64]| -> Vec<f64> {
+        let quantized: Vec<i64> = x.iter().map(|&v| spec.quantize(v)).collect();
+        spec.forward_fixed(&quantized)
+            .into_iter()
+            .map(|y| spec.dequantize(y))
+            .collect()
+    };
+    let max_diff = test_set
+        .features
+        .iter()
+        .flat_map(|x| {
+            model
+                .forward(x)
+                .into_iter()
+                .zip(fixed(x))
+                .map(|(a, b)| (a - b).abs())
+        })
+        .fold(0.0, f64::max);
+    let weights: Vec<f64> = spec
+        .layers
+        .iter()
+        .flat_map(|l| l.weights.iter().flatten())
+        .copied()
+        .collect();
+    println!(
+        "  mse: train {:.4}, test {:.4}, test fixed-point (2^{}) {:.4}; max |float - fixed| {:.2e}",
+        train_set.mse(|x| model.forward(x)),
+        test_set.mse(|x| model.forward(x)),
+        args.scale_bits,
+        test_set.mse(fixed),
+        max_diff
+    );
+    println!(
+        "  weights: {} values in [{:.4}, {:.4}], mean |w| {:.4}",
+        weights.len(),
+        weights.iter().copied().fold(f64::INFINITY, f64::min),
+        weights.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        weights.iter().map(|w| w.abs()).sum::<f64>() / weights.len() as f64
+    );
 
-    // MLP 예측 수행
-    let prediction = mlp_forward(x.clone(), &layers);
+    // 증명용 입력은 학습에 쓰지 않은 테스트 행의 앞부분
+    let input: Vec<Vec<f64>> = test_set
+        .features
+        .iter()
+        .take(args.samples)
+        .cloned()
+        .collect();
+    let dir = Path::new("model_data").join(format!("trained_{}", stem));
+    fs::create_dir_all(&dir).expect("unable to create output directory");
+    spec.save(dir.join("mlp_spec.json"))
+        .expect("Unable to write model spec");
+    spec.save_binary(dir.join("mlp_spec.bin"))
+        .expect("Unable to write binary model");
+    fs::write(
+        dir.join("mlp_input.json"),
+        serde_json::to_string(&input).unwrap(),
+    )
+    .expect("Unable to write data");
+    binary::save_input(dir.join("mlp_input.bin"), &input).expect("Unable to write binary input");
+    let workload = serde_json::json!({
+        "name": spec.name,
+        "layers": spec.layer_sizes(),
+        "input_file": "mlp_input.bin",
+        "spec": "mlp_spec.bin",
+    });
+    fs::write(
+        dir.join("workload.json"),
+        serde_json::to_string_pretty(&workload).unwrap(),
+    )
+    .expect("Unable to write workload");
+    println!("  {} -> {}", spec.name, dir.display());
+}
 
-    // 예측 결과 출력
-    for (i, pred) in prediction.row_iter().enumerate() {
-        println!("Prediction for input {}: {:?}", i, pred);
+// "3", "1,2,5" 또는 "1..13" 형태의 실험 번호 목록
+fn parse_exps(spec: &str) -> Vec<u32> {
+    match spec.split_once("..") {
+        Some((lo, hi)) => (lo.parse().unwrap()..=hi.parse().unwrap()).collect(),
+        None => spec.split(',').map(|s| s.trim().parse().unwrap()).collect(),
     }
+}
+
+// `--name value` 형태의 옵션 값
+fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
 
-    // 모델과 데이터를 파일로 저장
-    let model_bytes = rmp_serde::to_vec(&layers).unwrap();
-    let data_bytes = rmp_serde::to_vec(&x).unwrap();
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        eprintln!("usage: generate_data [scale_bits]");
+        eprintln!("       generate_data --exp 1..13 [--width 4] [--depth D] [--outputs K]");
+        eprintln!("                     [--init zeros|random|trained] [--seed 42] [--samples 1] [--scale-bits 16]");
+        eprintln!("       generate_data --train data.csv [--targets 1] [--hidden 16,16] [--optimizer adam|sgd]");
+        eprintln!("                     [--epochs 200] [--batch 32] [--lr 0.001] [--test 0.2] [--seed 42]");
+        eprintln!("                     [--samples 4] [--scale-bits 16]");
+        std::process::exit(2);
+    }
 
-    let model_json = serde_json::to_string(&model_bytes).unwrap();
-    let x_json = serde_json::to_string(&data_bytes).unwrap();
+    // --train: CSV 데이터셋으로 실제 학습한 MLP 를 내보냄
+    if let Some(csv) = flag_value(&args, "--train") {
+        let optimizer: Optimizer = flag_value(&args, "--optimizer")
+            .unwrap_or("adam")
+            .parse()
+            .unwrap_or_else(|e| panic!("{}", e));
+        let train = TrainArgs {
+            targets: flag_value(&args, "--targets")
+                .map(|s| s.parse().unwrap())
+                .unwrap_or(1),
+            config: TrainConfig {
+                hidden: flag_value(&args, "--hidden")
+                    .map(|s| s.split(',').map(|n| n.trim().parse().unwrap()).collect())
+                    .unwrap_or(vec![16, 16]),
+                epochs: flag_value(&args, "--epochs")
+                    .map(|s| s.parse().unwrap())
+                    .unwrap_or(200),
+                batch_size: flag_value(&args, "--batch")
+                    .map(|s| s.parse().unwrap())
+                    .unwrap_or(32),
+                // SGD 는 더 큰 학습률이 필요
+                learning_rate: flag_value(&args, "--lr")
+                    .map(|s| s.parse().unwrap())
+                    .unwrap_or(match optimizer {
+                        Optimizer::Adam => 0.001,
+                        Optimizer::Sgd => 0.01,
+                    }),
+                optimizer,
+            },
+            test_fraction: flag_value(&args, "--test")
+                .map(|s| s.parse().unwrap())
+                .unwrap_or(0.2),
+            seed: flag_value(&args, "--seed")
+                .map(|s| s.parse().unwrap())
+                .unwrap_or(42),
+            samples: flag_value(&args, "--samples")
+                .map(|s| s.parse().unwrap())
+                .unwrap_or(4),
+            scale_bits: flag_value(&args, "--scale-bits")
+                .map(|s| s.parse().unwrap())
+                .unwrap_or(DEFAULT_SCALE_BITS),
+        };
+        trained_model(Path::new(csv), &train);
+        return;
+    }
 
-    let mut f = File::create("model_data/mlp_model_bytes.json").expect("unable to create file");
-    f.write_all(model_json.as_bytes())
-        .expect("Unable to write data");
+    // 고정소수점 스케일 비트 수 (기본 16 = FP16x16)
+    let Some(exps) = flag_value(&args, "--exp") else {
+        let scale_bits: u32 = args
+            .get(1)
+            .map(|s| s.parse().expect("scale bits must be an integer"))
+            .unwrap_or(DEFAULT_SCALE_BITS);
+        legacy_model(scale_bits);
+        return;
+    };
 
-    let mut f1 =
-        File::create("model_data/mlp_input_data_bytes.json").expect("unable to create file");
-    f1.write_all(x_json.as_bytes())
-        .expect("Unable to write data");
+    let sweep = SweepArgs {
+        width: flag_value(&args, "--width")
+            .map(|s| s.parse().unwrap())
+            .unwrap_or(4),
+        depth: flag_value(&args, "--depth").map(|s| s.parse().unwrap()),
+        outputs: flag_value(&args, "--outputs")
+            .map(|s| s.parse().unwrap())
+            .unwrap_or(0),
+        init: match flag_value(&args, "--init").unwrap_or("random") {
+            "zeros" => Init::Zeros,
+            "random" => Init::Random,
+            "trained" => Init::Trained,
+            other => panic!("unknown init {} (zeros, random or trained)", other),
+        },
+        seed: flag_value(&args, "--seed")
+            .map(|s| s.parse().unwrap())
+            .unwrap_or(42),
+        samples: flag_value(&args, "--samples")
+            .map(|s| s.parse().unwrap())
+            .unwrap_or(1),
+        scale_bits: flag_value(&args, "--scale-bits")
+            .map(|s| s.parse().unwrap())
+            .unwrap_or(DEFAULT_SCALE_BITS),
+    };
+    for exp in parse_exps(exps) {
+        sweep_model(exp, &sweep);
+    }
 }
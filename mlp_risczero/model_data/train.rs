@@ -0,0 +1,543 @@
+//! Small pure-Rust MLP trainer for `generate_data --train`: ReLU hidden layers, a linear
+//! output layer, mean squared error, mini-batch SGD or Adam.
+//!
+//! Inputs and targets are standardized while training. The scaling is folded back into the
+//! first and last layers afterwards, so the exported model takes raw CSV values like any
+//! other spec.
+use model_spec::tensor::{Layer, Matrix};
+use model_spec::{Activation, FloatModel};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Numeric CSV table split into feature and target columns.
+pub struct Dataset {
+    pub columns: Vec<String>,
+    pub features: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+}
+
+impl Dataset {
+    /// CSV with a header row; the last `targets` columns are what the model predicts.
+    pub fn load_csv(path: &Path, targets: usize) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        let columns: Vec<String> = lines
+            .next()
+            .ok_or_else(|| format!("{} is empty", path.display()))?
+            .split(',')
+            .map(|c| c.trim().to_string())
+            .collect();
+        if targets == 0 || targets >= columns.len() {
+            return Err(format!(
+                "{} has {} columns, can't use the last {} as targets",
+                path.display(),
+                columns.len(),
+                targets
+            ));
+        }
+        let split = columns.len() - targets;
+        let (mut features, mut outputs) = (vec![], vec![]);
+        for (i, line) in lines.enumerate() {
+            let row = line
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("{} row {}: {}", path.display(), i + 1, e))?;
+            if row.len() != columns.len() {
+                return Err(format!(
+                    "{} row {} has {} values, header has {}",
+                    path.display(),
+                    i + 1,
+                    row.len(),
+                    columns.len()
+                ));
+            }
+            features.push(row[..split].to_vec());
+            outputs.push(row[split..].to_vec());
+        }
+        if features.is_empty() {
+            return Err(format!("{} has a header but no rows", path.display()));
+        }
+        Ok(Dataset {
+            columns,
+            features,
+            targets: outputs,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Shuffled (train, test) split with `test_fraction` of the rows held out. Both halves
+    /// must keep at least one row: training needs data and the proof inputs come from the test rows.
+    pub fn split(&self, test_fraction: f64, rng: &mut StdRng) -> Result<(Dataset, Dataset), String> {
+        if !(0.0..=1.0).contains(&test_fraction) {
+            return Err(format!("test fraction {} is not between 0 and 1", test_fraction));
+        }
+        let test = (self.len() as f64 * test_fraction).round() as usize;
+        if test == 0 || test == self.len() {
+            return Err(format!(
+                "test fraction {} of {} rows leaves {} train and {} test rows; both need at least one",
+                test_fraction,
+                self.len(),
+                self.len() - test,
+                test
+            ));
+        }
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.shuffle(rng);
+        let subset = |rows: &[usize]| Dataset {
+            columns: self.columns.clone(),
+            features: rows.iter().map(|&i| self.features[i].clone()).collect(),
+            targets: rows.iter().map(|&i| self.targets[i].clone()).collect(),
+        };
+        Ok((subset(&order[test..]), subset(&order[..test])))
+    }
+
+    /// Mean squared error of `predict` over every target value.
+    pub fn mse(&self, mut predict: impl FnMut(&[f64]) -> Vec<f64>) -> f64 {
+        let mut sum = 0.0;
+        let mut n = 0;
+        for (x, y) in self.features.iter().zip(&self.targets) {
+            for (p, t) in predict(x).iter().zip(y) {
+                sum += (p - t) * (p - t);
+                n += 1;
+            }
+        }
+        sum / n.max(1) as f64
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+    Sgd,
+    Adam,
+}
+
+impl FromStr for Optimizer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "sgd" => Ok(Optimizer::Sgd),
+            "adam" => Ok(Optimizer::Adam),
+            _ => Err(format!("unknown optimizer {} (sgd or adam)", s)),
+        }
+    }
+}
+
+pub struct TrainConfig {
+    /// Widths of the ReLU hidden layers.
+    pub hidden: Vec<usize>,
+    pub epochs: usize,
+    pub batch_size: usize,
+    pub learning_rate: f64,
+    pub optimizer: Optimizer,
+}
+
+/// Per-column mean and standard deviation (1 for constant columns).
+struct Scaler {
+    mean: Vec<f64>,
+    std: Vec<f64>,
+}
+
+impl Scaler {
+    fn fit(rows: &[Vec<f64>]) -> Self {
+        let n = rows.len().max(1) as f64;
+        let width = rows.first().map_or(0, |r| r.len());
+        let mean: Vec<f64> = (0..width)
+            .map(|j| rows.iter().map(|r| r[j]).sum::<f64>() / n)
+            .collect();
+        let std = (0..width)
+            .map(|j| {
+                let var = rows.iter().map(|r| (r[j] - mean[j]).powi(2)).sum::<f64>() / n;
+                if var > 0.0 {
+                    var.sqrt()
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+        Scaler { mean, std }
+    }
+
+    fn apply(&self, row: &[f64]) -> Vec<f64> {
+        row.iter()
+            .zip(self.mean.iter().zip(&self.std))
+            .map(|(x, (m, s))| (x - m) / s)
+            .collect()
+    }
+}
+
+// He 초기화 (ReLU 층), 출력 층은 Xavier. 편향은 0
+fn init_layer(
+    rng: &mut StdRng,
+    inputs: usize,
+    outputs: usize,
+    activation: Activation,
+) -> Layer<f64> {
+    let limit = match activation {
+        Activation::Relu => (6.0 / inputs as f64).sqrt(),
+        Activation::Linear => (6.0 / (inputs + outputs) as f64).sqrt(),
+    };
+    Layer {
+        activation,
+        weights: Matrix {
+            rows: inputs,
+            cols: outputs,
+            data: (0..inputs * outputs)
+                .map(|_| rng.gen_range(-limit..limit))
+                .collect(),
+        },
+        bias: vec![0.0; outputs],
+    }
+}
+
+fn zeros_like(layers: &[Layer<f64>]) -> Vec<Layer<f64>> {
+    layers
+        .iter()
+        .map(|l| Layer {
+            activation: l.activation,
+            weights: Matrix {
+                rows: l.weights.rows,
+                cols: l.weights.cols,
+                data: vec![0.0; l.weights.data.len()],
+            },
+            bias: vec![0.0; l.bias.len()],
+        })
+        .collect()
+}
+
+// 각 층의 출력을 모두 보관하는 forward: acts[0] 은 입력, acts[l + 1] 은 l 번째 층의 출력
+fn forward_all(layers: &[Layer<f64>], x: &[f64]) -> Vec<Vec<f64>> {
+    let mut acts = vec![x.to_vec()];
+    for layer in layers {
+        let next = layer.forward(acts.last().unwrap());
+        acts.push(next);
+    }
+    acts
+}
+
+// 한 샘플의 손실 기울기를 grads 에 더함. delta 는 마지막 층 출력에 대한 dL/dy
+fn backward(
+    layers: &[Layer<f64>],
+    acts: &[Vec<f64>],
+    mut delta: Vec<f64>,
+    grads: &mut [Layer<f64>],
+) {
+    for l in (0..layers.len()).rev() {
+        let layer = &layers[l];
+        // ReLU 미분: 출력이 0 이면 (입력이 0 이하) 기울기 0
+        if layer.activation == Activation::Relu {
+            for (d, &a) in delta.iter_mut().zip(&acts[l + 1]) {
+                if a <= 0.0 {
+                    *d = 0.0;
+                }
+            }
+        }
+        let grad = &mut grads[l];
+        for (b, d) in grad.bias.iter_mut().zip(&delta) {
+            *b += d;
+        }
+        let cols = grad.weights.cols;
+        for (k, &x) in acts[l].iter().enumerate() {
+            for (w, d) in grad.weights.data[k * cols..(k + 1) * cols]
+                .iter_mut()
+                .zip(&delta)
+            {
+                *w += x * d;
+            }
+        }
+        if l > 0 {
+            delta = (0..layer.inputs())
+                .map(|k| {
+                    layer
+                        .weights
+                        .row(k)
+                        .iter()
+                        .zip(&delta)
+                        .map(|(w, d)| w * d)
+                        .sum()
+                })
+                .collect();
+        }
+    }
+}
+
+/// Optimizer state; Adam keeps first and second moments per parameter tensor.
+struct Step {
+    optimizer: Optimizer,
+    learning_rate: f64,
+    t: i32,
+    m: Vec<Vec<f64>>,
+    v: Vec<Vec<f64>>,
+}
+
+impl Step {
+    const BETA1: f64 = 0.9;
+    const BETA2: f64 = 0.999;
+    const EPS: f64 = 1e-8;
+
+    fn apply(&mut self, layers: &mut [Layer<f64>], grads: &[Layer<f64>]) {
+        self.t += 1;
+        let params = layers
+            .iter_mut()
+            .flat_map(|l| [&mut l.weights.data, &mut l.bias]);
+        let grads = grads.iter().flat_map(|l| [&l.weights.data, &l.bias]);
+        for (i, (p, g)) in params.zip(grads).enumerate() {
+            match self.optimizer {
+                Optimizer::Sgd => {
+                    for (p, g) in p.iter_mut().zip(g) {
+                        *p -= self.learning_rate * g;
+                    }
+                }
+                Optimizer::Adam => {
+                    if self.m.len() <= i {
+                        self.m.push(vec![0.0; p.len()]);
+                        self.v.push(vec![0.0; p.len()]);
+                    }
+                    let correct1 = 1.0 - Self::BETA1.powi(self.t);
+                    let correct2 = 1.0 - Self::BETA2.powi(self.t);
+                    for ((p, g), (m, v)) in p
+                        .iter_mut()
+                        .zip(g)
+                        .zip(self.m[i].iter_mut().zip(self.v[i].iter_mut()))
+                    {
+                        *m = Self::BETA1 * *m + (1.0 - Self::BETA1) * g;
+                        *v = Self::BETA2 * *v + (1.0 - Self::BETA2) * g * g;
+                        *p -= self.learning_rate * (*m / correct1)
+                            / ((*v / correct2).sqrt() + Self::EPS);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Trains on `data` and returns the model in raw (unstandardized) units. `on_epoch` gets the
+/// epoch number and the training loss on standardized targets.
+pub fn train(
+    data: &Dataset,
+    config: &TrainConfig,
+    rng: &mut StdRng,
+    mut on_epoch: impl FnMut(usize, f64),
+) -> Result<FloatModel, String> {
+    if data.len() == 0 {
+        return Err("no training rows".to_string());
+    }
+    let x_scaler = Scaler::fit(&data.features);
+    let y_scaler = Scaler::fit(&data.targets);
+    let xs: Vec<Vec<f64>> = data.features.iter().map(|x| x_scaler.apply(x)).collect();
+    let ys: Vec<Vec<f64>> = data.targets.iter().map(|y| y_scaler.apply(y)).collect();
+
+    let mut sizes = vec![x_scaler.mean.len()];
+    sizes.extend(&config.hidden);
+    sizes.push(y_scaler.mean.len());
+    let mut layers: Vec<Layer<f64>> = sizes
+        .windows(2)
+        .enumerate()
+        .map(|(l, w)| {
+            let activation = if l + 2 < sizes.len() {
+                Activation::Relu
+            } else {
+                Activation::Linear
+            };
+            init_layer(rng, w[0], w[1], activation)
+        })
+        .collect();
+
+    let mut step = Step {
+        optimizer: config.optimizer,
+        learning_rate: config.learning_rate,
+        t: 0,
+        m: vec![],
+        v: vec![],
+    };
+    let mut order: Vec<usize> = (0..xs.len()).collect();
+    for epoch in 1..=config.epochs {
+        order.shuffle(rng);
+        let mut loss = 0.0;
+        for batch in order.chunks(config.batch_size.max(1)) {
+            let mut grads = zeros_like(&layers);
+            // 배치 평균 MSE 의 기울기: 2 (y - t) / (배치 크기 * 출력 수)
+            let scale = 2.0 / (batch.len() * ys[0].len()) as f64;
+            for &i in batch {
+                let acts = forward_all(&layers, &xs[i]);
+                let delta: Vec<f64> = acts
+                    .last()
+                    .unwrap()
+                    .iter()
+                    .zip(&ys[i])
+                    .map(|(y, t)| {
+                        loss += (y - t) * (y - t);
+                        scale * (y - t)
+                    })
+                    .collect();
+                backward(&layers, &acts, delta, &mut grads);
+            }
+            step.apply(&mut layers, &grads);
+        }
+        on_epoch(epoch, loss / (xs.len() * ys[0].len()) as f64);
+    }
+
+    fold_scaling(&mut layers, &x_scaler, &y_scaler);
+    Ok(FloatModel {
+        input_size: sizes[0],
+        layers,
+    })
+}
+
+// 표준화를 첫 층과 마지막 층에 접어 넣어 원래 단위의 입력/출력으로 동작하게 함
+fn fold_scaling(layers: &mut [Layer<f64>], x_scaler: &Scaler, y_scaler: &Scaler) {
+    let first = &mut layers[0];
+    for j in 0..first.outputs() {
+        let mut shift = 0.0;
+        for k in 0..first.inputs() {
+            let w = first.weights.get(k, j) / x_scaler.std[k];
+            first.weights.set(k, j, w);
+            shift += w * x_scaler.mean[k];
+        }
+        first.bias[j] -= shift;
+    }
+    let last = layers.last_mut().unwrap();
+    for j in 0..last.outputs() {
+        for k in 0..last.inputs() {
+            last.weights
+                .set(k, j, last.weights.get(k, j) * y_scaler.std[j]);
+        }
+        last.bias[j] = last.bias[j] * y_scaler.std[j] + y_scaler.mean[j];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn network(rng: &mut StdRng) -> Vec<Layer<f64>> {
+        let mut layers = vec![
+            init_layer(rng, 3, 4, Activation::Relu),
+            init_layer(rng, 4, 2, Activation::Linear),
+        ];
+        // 편향이 0 이면 ReLU 경계에 걸리기 쉬우므로 작은 값을 넣음
+        for b in layers.iter_mut().flat_map(|l| l.bias.iter_mut()) {
+            *b = rng.gen_range(-0.5..0.5);
+        }
+        layers
+    }
+
+    // which 0 = 가중치, 1 = 편향
+    fn param(layers: &mut [Layer<f64>], l: usize, which: usize, i: usize) -> &mut f64 {
+        if which == 0 {
+            &mut layers[l].weights.data[i]
+        } else {
+            &mut layers[l].bias[i]
+        }
+    }
+
+    fn loss(layers: &[Layer<f64>], x: &[f64], t: &[f64]) -> f64 {
+        let acts = forward_all(layers, x);
+        acts.last().unwrap().iter().zip(t).map(|(y, t)| (y - t) * (y - t)).sum()
+    }
+
+    #[test]
+    fn backward_matches_finite_differences() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut layers = network(&mut rng);
+        let (x, t) = (vec![0.8, -1.3, 0.4], vec![0.5, -0.25]);
+
+        let acts = forward_all(&layers, &x);
+        let delta = acts.last().unwrap().iter().zip(&t).map(|(y, t)| 2.0 * (y - t)).collect();
+        let mut grads = zeros_like(&layers);
+        backward(&layers, &acts, delta, &mut grads);
+
+        // 중앙 차분 (L(p + h) - L(p - h)) / 2h 와 모든 가중치/편향의 해석적 기울기 비교
+        let h = 1e-6;
+        for l in 0..layers.len() {
+            for which in 0..2 {
+                let n = if which == 0 { layers[l].weights.data.len() } else { layers[l].bias.len() };
+                for i in 0..n {
+                    let original = *param(&mut layers, l, which, i);
+                    *param(&mut layers, l, which, i) = original + h;
+                    let plus = loss(&layers, &x, &t);
+                    *param(&mut layers, l, which, i) = original - h;
+                    let minus = loss(&layers, &x, &t);
+                    *param(&mut layers, l, which, i) = original;
+
+                    let numeric = (plus - minus) / (2.0 * h);
+                    let analytic = if which == 0 { grads[l].weights.data[i] } else { grads[l].bias[i] };
+                    assert!(
+                        (numeric - analytic).abs() < 1e-6 * (1.0 + numeric.abs()),
+                        "layer {} {} {}: numeric {} analytic {}",
+                        l,
+                        if which == 0 { "weight" } else { "bias" },
+                        i,
+                        numeric,
+                        analytic
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn folded_scaling_matches_the_standardized_model() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let features: Vec<Vec<f64>> = (0..20)
+            .map(|_| vec![rng.gen_range(-50.0..50.0), rng.gen_range(0.0..3.0), 7.0])
+            .collect();
+        let targets: Vec<Vec<f64>> = (0..20)
+            .map(|_| vec![rng.gen_range(100.0..200.0), rng.gen_range(-1.0..1.0)])
+            .collect();
+        let (x_scaler, y_scaler) = (Scaler::fit(&features), Scaler::fit(&targets));
+        let layers = network(&mut rng);
+        let mut folded = layers.clone();
+        fold_scaling(&mut folded, &x_scaler, &y_scaler);
+
+        for x in &features {
+            let standardized = forward_all(&layers, &x_scaler.apply(x)).pop().unwrap();
+            let expected: Vec<f64> = standardized
+                .iter()
+                .zip(y_scaler.mean.iter().zip(&y_scaler.std))
+                .map(|(y, (m, s))| y * s + m)
+                .collect();
+            let got = forward_all(&folded, x).pop().unwrap();
+            for (a, b) in got.iter().zip(&expected) {
+                assert!((a - b).abs() < 1e-9 * (1.0 + b.abs()), "{:?} vs {:?}", got, expected);
+            }
+        }
+    }
+
+    fn dataset(rows: usize) -> Dataset {
+        Dataset {
+            columns: vec!["x".to_string(), "y".to_string()],
+            features: (0..rows).map(|i| vec![i as f64]).collect(),
+            targets: (0..rows).map(|i| vec![2.0 * i as f64]).collect(),
+        }
+    }
+
+    #[test]
+    fn split_rejects_empty_halves() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (train, test) = dataset(10).split(0.2, &mut rng).unwrap();
+        assert_eq!((train.len(), test.len()), (8, 2));
+        assert!(dataset(10).split(1.0, &mut rng).is_err());
+        assert!(dataset(10).split(0.0, &mut rng).is_err());
+        assert!(dataset(1).split(0.2, &mut rng).is_err());
+    }
+
+    #[test]
+    fn header_only_csv_is_rejected() {
+        let path = std::env::temp_dir().join(format!("train_header_only_{}.csv", std::process::id()));
+        fs::write(&path, "a,b,target\n").unwrap();
+        let result = Dataset::load_csv(&path, 1);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(e) if e.contains("no rows")));
+    }
+}
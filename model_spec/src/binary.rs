@@ -0,0 +1,624 @@
+//! Versioned binary files for models and input rows, next to the JSON spec.
+//!
+//! JSON is kept as the human-readable, Python-facing form. This format exists for loading speed
+//! and size: values are stored as raw little-endian 8-byte words instead of decimal text, and
+//! the RISC Zero guest reads and hashes the file bytes as-is.
+//!
+//! Every file starts with a 32-byte header; all integers are little-endian:
+//!
+//! | offset | size | field                                                        |
+//! |--------|------|--------------------------------------------------------------|
+//! | 0      | 4    | magic `MLPB`                                                 |
+//! | 4      | 2    | format version (`FORMAT_VERSION`)                            |
+//! | 6      | 1    | kind: 1 = model, 2 = input rows                              |
+//! | 7      | 1    | dtype: 1 = `f64`, 2 = `i64` fixed point                      |
+//! | 8      | 4    | `scale_bits`, or `NO_SCALE` if the model has no quantization |
+//! | 12     | 4    | count: layers (model) or rows (input)                        |
+//! | 16     | 4    | width: `input_size` (model) or values per row (input)        |
+//! | 20     | 4    | payload length in bytes                                      |
+//! | 24     | 4    | CRC-32 (IEEE) of the payload                                 |
+//! | 28     | 4    | CRC-32 (IEEE) of header bytes 0..28                          |
+//!
+//! A model payload is the name (a `u32` length, then UTF-8 bytes zero-padded to 4 bytes).
+//! Then, per layer, come `inputs`, `outputs` and `activation` (0 = linear, 1 = relu) as `u32`s,
+//! followed by `inputs x outputs` weights row after row and `outputs` biases. An input
+//! payload is `count x width` values, row after row. Every section is a multiple of 4 bytes,
+//! so a file can be sent to the guest as whole words.
+//!
+//! Both checksums are verified before any count or width is used to size an allocation, and
+//! counts are also bounded by what the payload can hold.
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::tensor::{Layer, Matrix};
+use crate::{Activation, Dense, FloatModel, ModelSpec, Quantization, QuantizedModel, SpecError, SPEC_VERSION};
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+pub const MAGIC: [u8; 4] = *b"MLPB";
+
+/// Binary format version this crate reads and writes. Version 2 added the header checksum.
+pub const FORMAT_VERSION: u16 = 2;
+
+pub const HEADER_SIZE: usize = 32;
+
+/// `scale_bits` field of a model without quantization.
+pub const NO_SCALE: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Model = 1,
+    Input = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DType {
+    /// Raw `f64` bits.
+    F64 = 1,
+    /// Values already rounded to `round(x * 2^scale_bits)`.
+    I64 = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u16,
+    pub kind: Kind,
+    pub dtype: DType,
+    pub scale_bits: Option<u32>,
+    pub count: u32,
+    pub width: u32,
+    pub payload_len: u32,
+    pub crc32: u32,
+}
+
+impl Header {
+    fn to_bytes(self) -> [u8; HEADER_SIZE] {
+        let mut out = [0u8; HEADER_SIZE];
+        out[0..4].copy_from_slice(&MAGIC);
+        out[4..6].copy_from_slice(&self.version.to_le_bytes());
+        out[6] = self.kind as u8;
+        out[7] = self.dtype as u8;
+        out[8..12].copy_from_slice(&self.scale_bits.unwrap_or(NO_SCALE).to_le_bytes());
+        out[12..16].copy_from_slice(&self.count.to_le_bytes());
+        out[16..20].copy_from_slice(&self.width.to_le_bytes());
+        out[20..24].copy_from_slice(&self.payload_len.to_le_bytes());
+        out[24..28].copy_from_slice(&self.crc32.to_le_bytes());
+        let header_crc = crc32(&out[..28]);
+        out[28..32].copy_from_slice(&header_crc.to_le_bytes());
+        out
+    }
+
+    /// Parses and checks the header, returning it with the payload once the header checksum,
+    /// payload length and payload checksum match.
+    pub fn parse(bytes: &[u8]) -> Result<(Header, &[u8]), SpecError> {
+        if bytes.len() < HEADER_SIZE || bytes[0..4] != MAGIC {
+            return Err(format_error("not a binary model/input file (bad magic)"));
+        }
+        let word = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != FORMAT_VERSION {
+            return Err(format_error(&format!(
+                "unsupported binary format version {} (expected {})",
+                version, FORMAT_VERSION
+            )));
+        }
+        // count/width/payload_len 은 페이로드 CRC 밖이므로 헤더 자체의 CRC 를 먼저 확인
+        let header_crc = crc32(&bytes[..28]);
+        if header_crc != word(28) {
+            return Err(format_error(&format!(
+                "header checksum mismatch: crc32 {:08x}, stored {:08x}",
+                header_crc,
+                word(28)
+            )));
+        }
+        let kind = match bytes[6] {
+            1 => Kind::Model,
+            2 => Kind::Input,
+            k => return Err(format_error(&format!("unknown file kind {}", k))),
+        };
+        let dtype = match bytes[7] {
+            1 => DType::F64,
+            2 => DType::I64,
+            d => return Err(format_error(&format!("unknown dtype {}", d))),
+        };
+        let header = Header {
+            version,
+            kind,
+            dtype,
+            scale_bits: Some(word(8)).filter(|&s| s != NO_SCALE),
+            count: word(12),
+            width: word(16),
+            payload_len: word(20),
+            crc32: word(24),
+        };
+        let payload = &bytes[HEADER_SIZE..];
+        if payload.len() != header.payload_len as usize {
+            return Err(format_error(&format!(
+                "payload is {} bytes, header says {}",
+                payload.len(),
+                header.payload_len
+            )));
+        }
+        let crc = crc32(payload);
+        if crc != header.crc32 {
+            return Err(format_error(&format!(
+                "checksum mismatch: payload crc32 {:08x}, header {:08x}",
+                crc, header.crc32
+            )));
+        }
+        Ok((header, payload))
+    }
+}
+
+fn format_error(message: &str) -> SpecError {
+    SpecError::Format(message.to_string())
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE 802.3, as in zlib and PNG).
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |c, &b| CRC_TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+fn finish(kind: Kind, dtype: DType, scale_bits: Option<u32>, count: usize, width: usize, payload: Vec<u8>) -> Vec<u8> {
+    let header = Header {
+        version: FORMAT_VERSION,
+        kind,
+        dtype,
+        scale_bits,
+        count: count as u32,
+        width: width as u32,
+        payload_len: payload.len() as u32,
+        crc32: crc32(&payload),
+    };
+    let mut out = Vec::with_capacity(HEADER_SIZE + payload.len());
+    out.extend_from_slice(&header.to_bytes());
+    out.extend_from_slice(&payload);
+    out
+}
+
+/// Sequential little-endian reader over a payload.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SpecError> {
+        let end = self.at.checked_add(n).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(|| format_error("payload ends early"))?;
+        let slice = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, SpecError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn values<T>(&mut self, n: usize, read: fn([u8; 8]) -> T) -> Result<Vec<T>, SpecError> {
+        let len = n.checked_mul(8).ok_or_else(|| format_error("value count overflows"))?;
+        let bytes = self.take(len)?;
+        Ok((0..n).map(|i| read(bytes[8 * i..8 * i + 8].try_into().unwrap())).collect())
+    }
+
+    fn done(&self) -> Result<(), SpecError> {
+        if self.at != self.bytes.len() {
+            return Err(format_error(&format!("{} trailing payload bytes", self.bytes.len() - self.at)));
+        }
+        Ok(())
+    }
+}
+
+/// A decoded model payload before it is turned into a `ModelSpec` or a guest model.
+struct RawModel<T> {
+    header: Header,
+    name: String,
+    layers: Vec<Layer<T>>,
+}
+
+fn encode_model<T: Copy>(
+    spec: &ModelSpec,
+    dtype: DType,
+    value: impl Fn(f64) -> T,
+    bytes: fn(T) -> [u8; 8],
+) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&(spec.name.len() as u32).to_le_bytes());
+    payload.extend_from_slice(spec.name.as_bytes());
+    payload.resize(payload.len().next_multiple_of(4), 0);
+    for layer in &spec.layers {
+        for n in [layer.inputs(), layer.outputs] {
+            payload.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        let activation: u32 = match layer.activation {
+            Activation::Linear => 0,
+            Activation::Relu => 1,
+        };
+        payload.extend_from_slice(&activation.to_le_bytes());
+        for &w in layer.weights.iter().flatten().chain(&layer.bias) {
+            payload.extend_from_slice(&bytes(value(w)));
+        }
+    }
+    // i64 가중치는 quantization 이 없어도 DEFAULT_SCALE_BITS 로 양자화되므로 실제 스케일을 기록
+    let scale_bits = match dtype {
+        DType::F64 => spec.quantization.map(|q| q.scale_bits),
+        DType::I64 => Some(spec.scale_bits()),
+    };
+    finish(Kind::Model, dtype, scale_bits, spec.layers.len(), spec.input_size, payload)
+}
+
+fn decode_model<T>(bytes: &[u8], dtype: DType, read: fn([u8; 8]) -> T) -> Result<RawModel<T>, SpecError> {
+    let (header, payload) = Header::parse(bytes)?;
+    if header.kind != Kind::Model {
+        return Err(format_error("expected a model file, found input rows"));
+    }
+    if header.dtype != dtype {
+        return Err(format_error(&format!("model is stored as {:?}, expected {:?}", header.dtype, dtype)));
+    }
+    let mut reader = Reader { bytes: payload, at: 0 };
+    let name_len = reader.u32()? as usize;
+    let name = reader.take(name_len.next_multiple_of(4))?[..name_len].to_vec();
+    let name = String::from_utf8(name).map_err(|_| format_error("model name is not UTF-8"))?;
+
+    // 층마다 최소 12바이트(inputs, outputs, activation)이므로 그보다 많은 층 수는 할당 전에 거부
+    if header.count as usize > payload.len() / 12 {
+        return Err(format_error(&format!(
+            "{} layers do not fit in a {}-byte payload",
+            header.count,
+            payload.len()
+        )));
+    }
+    let mut width = header.width as usize;
+    let mut layers = Vec::with_capacity(header.count as usize);
+    for i in 0..header.count {
+        let (inputs, outputs) = (reader.u32()? as usize, reader.u32()? as usize);
+        let activation = match reader.u32()? {
+            0 => Activation::Linear,
+            1 => Activation::Relu,
+            a => return Err(format_error(&format!("layer {} has unknown activation {}", i, a))),
+        };
+        if inputs != width {
+            return Err(SpecError::Shape(format!(
+                "layer {} has {} inputs, previous layer outputs {}",
+                i, inputs, width
+            )));
+        }
+        let size = inputs.checked_mul(outputs).ok_or_else(|| format_error("layer size overflows"))?;
+        layers.push(Layer {
+            activation,
+            weights: Matrix {
+                rows: inputs,
+                cols: outputs,
+                data: reader.values(size, read)?,
+            },
+            bias: reader.values(outputs, read)?,
+        });
+        width = outputs;
+    }
+    reader.done()?;
+    if layers.is_empty() {
+        return Err(SpecError::Shape("model has no layers".to_string()));
+    }
+    Ok(RawModel { header, name, layers })
+}
+
+impl ModelSpec {
+    /// Binary model file with `f64` weights; see the module docs for the layout.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_model(self, DType::F64, |w| w, f64::to_le_bytes)
+    }
+
+    /// Binary model file with weights and biases already quantized to the spec's scale, for a
+    /// fixed-point guest.
+    #[cfg(feature = "std")]
+    pub fn to_fixed_bytes(&self) -> Vec<u8> {
+        encode_model(self, DType::I64, |w| self.quantize(w), i64::to_le_bytes)
+    }
+
+    /// Reads either dtype. Fixed-point weights are divided back by `2^scale_bits`, which is
+    /// exact, so quantizing the result gives the stored integers again.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SpecError> {
+        let raw = match Header::parse(bytes)?.0.dtype {
+            DType::F64 => decode_model(bytes, DType::F64, f64::from_le_bytes)?,
+            DType::I64 => {
+                let raw = decode_model(bytes, DType::I64, i64::from_le_bytes)?;
+                let scale = (1u64 << raw.header.scale_bits.unwrap_or(0)) as f64;
+                RawModel {
+                    header: raw.header,
+                    name: raw.name,
+                    layers: raw
+                        .layers
+                        .into_iter()
+                        .map(|l| Layer {
+                            activation: l.activation,
+                            weights: Matrix {
+                                rows: l.weights.rows,
+                                cols: l.weights.cols,
+                                data: l.weights.data.iter().map(|&w| w as f64 / scale).collect(),
+                            },
+                            bias: l.bias.iter().map(|&b| b as f64 / scale).collect(),
+                        })
+                        .collect(),
+                }
+            }
+        };
+        let spec = ModelSpec {
+            version: SPEC_VERSION,
+            name: raw.name,
+            input_size: raw.header.width as usize,
+            quantization: raw.header.scale_bits.map(|scale_bits| Quantization { scale_bits }),
+            layers: raw
+                .layers
+                .into_iter()
+                .map(|l| Dense {
+                    outputs: l.outputs(),
+                    activation: l.activation,
+                    weights: l.weights.to_rows(),
+                    bias: l.bias,
+                })
+                .collect(),
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save_binary(&self, path: impl AsRef<Path>) -> Result<(), SpecError> {
+        let path = path.as_ref();
+        fs::write(path, self.to_bytes()).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))
+    }
+}
+
+impl FloatModel {
+    /// Decodes an `f64` model file straight into flat layers, without building a `ModelSpec`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SpecError> {
+        let raw = decode_model(bytes, DType::F64, f64::from_le_bytes)?;
+        Ok(FloatModel {
+            input_size: raw.header.width as usize,
+            layers: raw.layers,
+        })
+    }
+}
+
+impl QuantizedModel {
+    /// Decodes a fixed-point model file written by `ModelSpec::to_fixed_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SpecError> {
+        let raw = decode_model(bytes, DType::I64, i64::from_le_bytes)?;
+        let scale_bits = raw
+            .header
+            .scale_bits
+            .ok_or_else(|| format_error("fixed-point model has no scale_bits"))?;
+        Ok(QuantizedModel {
+            scale_bits,
+            input_size: raw.header.width as usize,
+            layers: raw.layers,
+        })
+    }
+}
+
+/// Binary input file: `rows.len()` rows of `f64`. Rows must all be as wide as the first,
+/// like `Matrix::from_rows`.
+pub fn encode_input(rows: &[Vec<f64>]) -> Result<Vec<u8>, SpecError> {
+    let width = rows.first().map_or(0, |r| r.len());
+    if let Some(i) = rows.iter().position(|r| r.len() != width) {
+        return Err(SpecError::Shape(format!(
+            "input row {} has {} values, row 0 has {}",
+            i,
+            rows[i].len(),
+            width
+        )));
+    }
+    let payload = rows.iter().flatten().flat_map(|x| x.to_le_bytes()).collect();
+    Ok(finish(Kind::Input, DType::F64, None, rows.len(), width, payload))
+}
+
+pub fn decode_input(bytes: &[u8]) -> Result<Vec<Vec<f64>>, SpecError> {
+    Ok(input_rows(bytes)?.1.collect())
+}
+
+/// Checks an `f64` input file and returns its header with the rows, decoded one at a time so
+/// a guest can run each sample without holding them all.
+pub fn input_rows(bytes: &[u8]) -> Result<(Header, impl Iterator<Item = Vec<f64>> + '_), SpecError> {
+    let (header, payload) = Header::parse(bytes)?;
+    if header.kind != Kind::Input || header.dtype != DType::F64 {
+        return Err(format_error("expected an f64 input file"));
+    }
+    let (count, width) = (header.count as usize, header.width as usize);
+    // 너비 0 이면 페이로드 길이로 행 수를 제한할 수 없으므로 빈 파일만 허용
+    if width == 0 && count > 0 {
+        return Err(format_error(&format!("{} input rows of width 0", count)));
+    }
+    let size = count
+        .checked_mul(width)
+        .and_then(|n| n.checked_mul(8))
+        .ok_or_else(|| format_error("input size overflows"))?;
+    if payload.len() != size {
+        return Err(format_error(&format!(
+            "payload is {} bytes, {} rows of {} values need {}",
+            payload.len(),
+            count,
+            width,
+            size
+        )));
+    }
+    let rows = (0..count).map(move |i| {
+        let row = &payload[i * width * 8..(i + 1) * width * 8];
+        (0..width)
+            .map(|k| f64::from_le_bytes(row[k * 8..k * 8 + 8].try_into().unwrap()))
+            .collect()
+    });
+    Ok((header, rows))
+}
+
+/// Input rows from a binary file or, if it lacks the magic, a JSON array of rows.
+#[cfg(feature = "std")]
+pub fn load_input(path: impl AsRef<Path>) -> Result<Vec<Vec<f64>>, SpecError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))?;
+    if bytes.starts_with(&MAGIC) {
+        decode_input(&bytes)
+    } else {
+        serde_json::from_slice(&bytes).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn save_input(path: impl AsRef<Path>, rows: &[Vec<f64>]) -> Result<(), SpecError> {
+    let path = path.as_ref();
+    fs::write(path, encode_input(rows)?).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ModelSpec {
+        ModelSpec {
+            version: SPEC_VERSION,
+            name: "tiny".to_string(),
+            input_size: 2,
+            quantization: Some(Quantization { scale_bits: 8 }),
+            layers: vec![
+                Dense {
+                    outputs: 3,
+                    activation: Activation::Relu,
+                    weights: vec![vec![0.5, -1.25, 2.0], vec![0.75, 0.0, -0.125]],
+                    bias: vec![0.125, -0.25, 0.375],
+                },
+                Dense {
+                    outputs: 1,
+                    activation: Activation::Linear,
+                    weights: vec![vec![1.0], vec![-0.5], vec![0.25]],
+                    bias: vec![0.0],
+                },
+            ],
+        }
+    }
+
+    fn is_format_error(result: Result<impl core::fmt::Debug, SpecError>, needle: &str) -> bool {
+        matches!(result, Err(SpecError::Format(message)) if message.contains(needle))
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn f64_model_round_trips() {
+        let spec = spec();
+        let bytes = spec.to_bytes();
+        assert_eq!(ModelSpec::from_bytes(&bytes).unwrap(), spec);
+        let model = FloatModel::from_bytes(&bytes).unwrap();
+        assert_eq!(model, spec.float_model());
+    }
+
+    #[test]
+    fn i64_model_round_trips() {
+        let spec = spec();
+        let bytes = spec.to_fixed_bytes();
+        assert_eq!(QuantizedModel::from_bytes(&bytes).unwrap(), spec.quantized());
+        // 가중치와 편향이 2^-8 의 배수라 f64 로 되돌려도 정확히 같음
+        assert_eq!(ModelSpec::from_bytes(&bytes).unwrap(), spec);
+        assert!(is_format_error(FloatModel::from_bytes(&bytes), "stored as I64"));
+    }
+
+    #[test]
+    fn fixed_model_without_quantization_records_the_default_scale() {
+        let spec = ModelSpec {
+            quantization: None,
+            ..spec()
+        };
+        let bytes = spec.to_fixed_bytes();
+        let model = QuantizedModel::from_bytes(&bytes).unwrap();
+        assert_eq!(model.scale_bits, crate::DEFAULT_SCALE_BITS);
+        assert_eq!(model, spec.quantized());
+        // 스케일이 헤더에 있으므로 f64 로 되돌리면 원래 가중치 (0.5 가 32768.0 이 되지 않음)
+        let decoded = ModelSpec::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.layers, spec.layers);
+        assert_eq!(decoded.quantization, Some(Quantization { scale_bits: crate::DEFAULT_SCALE_BITS }));
+    }
+
+    #[test]
+    fn input_round_trips() {
+        let rows = vec![vec![1.0, -2.5], vec![f64::MAX, 1e-300], vec![0.0, -0.0]];
+        let bytes = encode_input(&rows).unwrap();
+        assert_eq!(decode_input(&bytes).unwrap(), rows);
+        assert_eq!(decode_input(&encode_input(&[]).unwrap()).unwrap(), Vec::<Vec<f64>>::new());
+    }
+
+    #[test]
+    fn ragged_input_is_rejected() {
+        let rows = vec![vec![1.0, 2.0], vec![3.0]];
+        assert!(matches!(encode_input(&rows), Err(SpecError::Shape(_))));
+    }
+
+    #[test]
+    fn flipped_payload_byte_fails_the_checksum() {
+        for mut bytes in [spec().to_bytes(), encode_input(&[vec![1.0, 2.0]]).unwrap()] {
+            let last = bytes.len() - 1;
+            bytes[last] ^= 0x01;
+            assert!(is_format_error(Header::parse(&bytes).map(|(h, _)| h), "checksum mismatch"));
+        }
+    }
+
+    #[test]
+    fn flipped_header_byte_fails_the_header_checksum() {
+        let mut bytes = spec().to_bytes();
+        // count 필드를 거의 2^32 로 바꿔도 할당 전에 거부됨
+        bytes[15] = 0xff;
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes), "header checksum"));
+    }
+
+    #[test]
+    fn truncated_file_is_rejected() {
+        let bytes = spec().to_bytes();
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes[..bytes.len() - 8]), "payload is"));
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes[..HEADER_SIZE - 1]), "bad magic"));
+        let input = encode_input(&[vec![1.0, 2.0]]).unwrap();
+        assert!(is_format_error(decode_input(&input[..input.len() - 1]), "payload is"));
+    }
+
+    #[test]
+    fn bad_magic_and_version_are_rejected() {
+        let mut bytes = spec().to_bytes();
+        bytes[0] = b'X';
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes), "bad magic"));
+
+        let mut bytes = spec().to_bytes();
+        bytes[4..6].copy_from_slice(&1u16.to_le_bytes());
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes), "unsupported binary format version 1"));
+    }
+
+    #[test]
+    fn oversized_counts_are_rejected_before_allocating() {
+        // 헤더 CRC 까지 맞춘 악의적인 파일: count 가 페이로드에 들어갈 수 있는 층 수보다 큼
+        let spec = spec();
+        let payload = spec.to_bytes()[HEADER_SIZE..].to_vec();
+        let bytes = finish(Kind::Model, DType::F64, None, u32::MAX as usize, 2, payload);
+        assert!(is_format_error(ModelSpec::from_bytes(&bytes), "do not fit"));
+
+        let bytes = finish(Kind::Input, DType::F64, None, u32::MAX as usize, 0, Vec::new());
+        assert!(is_format_error(decode_input(&bytes), "width 0"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Checks that backends compute the same function as the reference forward pass.
+//!
+//! ```text
+//! equivcheck --spec spec.json --input input.json
+//!            [--kimchi out.json] [--risc0 out.json] [--ezkl witness.json]
+//!            [--atol 1e-3] [--rtol 1e-3] [--tol ezkl:0.25:0.05 ...]
+//! ```
+//!
+//! Backend outputs are `{"outputs": [[...], ...]}` files (`--dump-outputs` of kimchi_exp
+//! and the RISC Zero host) or ezkl's `witness.json`. An element passes when
+//! `|got - ref| <= atol + rtol * |ref|`. Exits with 1 if any backend disagrees.
+use model_spec::ModelSpec;
+use serde_json::Value;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process;
+
+type Rows = Vec<Vec<f64>>;
+
+#[derive(Clone, Copy)]
+struct Tolerance {
+    atol: f64,
+    rtol: f64,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: equivcheck --spec SPEC --input INPUT [--kimchi FILE] [--risc0 FILE] [--ezkl WITNESS]");
+    eprintln!("                  [--atol X] [--rtol X] [--tol backend:atol:rtol ...]");
+    process::exit(2);
+}
+
+// `--name value` 형태의 옵션 값
+fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+fn read_json(path: &str) -> Result<Value, Box<dyn Error>> {
+    let text = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+    Ok(serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e))?)
+}
+
+fn rows(value: &Value, what: &str) -> Result<Rows, Box<dyn Error>> {
+    let parsed: Rows =
+        serde_json::from_value(value.clone()).map_err(|e| format!("{} is not a list of rows: {}", what, e))?;
+    Ok(parsed)
+}
+
+/// `[[...]]`, `{"input_data": [[...]]}` (ezkl) or `{"input": [[...]]}` (workload).
+fn load_input(path: &str) -> Result<Rows, Box<dyn Error>> {
+    let json = read_json(path)?;
+    let value = json.get("input_data").or_else(|| json.get("input")).unwrap_or(&json);
+    rows(value, path)
+}
+
+fn load_outputs(path: &str) -> Result<Rows, Box<dyn Error>> {
+    let json = read_json(path)?;
+    rows(json.get("outputs").unwrap_or(&json), path)
+}
+
+/// ezkl keeps field elements in `outputs` and their float values in
+/// `pretty_elements.rescaled_outputs`, one flattened list per output tensor.
+/// Returns (outputs, inputs).
+fn load_ezkl(path: &str, output_size: usize) -> Result<(Rows, Rows), Box<dyn Error>> {
+    let json = read_json(path)?;
+    let pretty = json
+        .get("pretty_elements")
+        .ok_or_else(|| format!("{} has no pretty_elements (run gen-witness with a recent ezkl)", path))?;
+
+    let flatten = |key: &str| -> Result<Vec<f64>, Box<dyn Error>> {
+        let tensors: Vec<Vec<String>> = serde_json::from_value(pretty.get(key).cloned().unwrap_or(Value::Null))
+            .map_err(|e| format!("{}: bad {}: {}", path, key, e))?;
+        let mut values = vec![];
+        for s in tensors.iter().flatten() {
+            values.push(s.parse::<f64>().map_err(|e| format!("{}: {} in {}: {}", path, s, key, e))?);
+        }
+        Ok(values)
+    };
+    let outputs = flatten("rescaled_outputs")?;
+    let inputs = flatten("rescaled_inputs")?;
+    Ok((
+        outputs.chunks(output_size.max(1)).map(|c| c.to_vec()).collect(),
+        vec![inputs],
+    ))
+}
+
+/// Compares `got` with `reference`; prints mismatches and returns whether all passed.
+fn compare(name: &str, got: &[Vec<f64>], reference: &[Vec<f64>], tol: Tolerance) -> bool {
+    if got.len() != reference.len() || got.iter().zip(reference).any(|(g, r)| g.len() != r.len()) {
+        let shape = |rows: &[Vec<f64>]| rows.iter().map(|r| r.len()).collect::<Vec<_>>();
+        println!(
+            "{:<10} FAIL shape {:?} != reference {:?}",
+            name,
+            shape(got),
+            shape(reference)
+        );
+        return false;
+    }
+
+    let mut max_abs = 0.0f64;
+    let mut max_rel = 0.0f64;
+    let mut failures = vec![];
+    for (i, (g_row, r_row)) in got.iter().zip(reference).enumerate() {
+        for (j, (&g, &r)) in g_row.iter().zip(r_row).enumerate() {
+            let abs = (g - r).abs();
+            let rel = if r == 0.0 { abs } else { abs / r.abs() };
+            max_abs = max_abs.max(abs);
+            max_rel = max_rel.max(rel);
+            if abs > tol.atol + tol.rtol * r.abs() || g.is_nan() {
+                failures.push((i, j, g, r, abs, rel));
+            }
+        }
+    }
+
+    println!(
+        "{:<10} {} max_abs {:.3e} max_rel {:.3e} (atol {:.1e}, rtol {:.1e})",
+        name,
+        if failures.is_empty() { "ok  " } else { "FAIL" },
+        max_abs,
+        max_rel,
+        tol.atol,
+        tol.rtol
+    );
+    for (i, j, g, r, abs, rel) in &failures {
+        println!(
+            "    sample {} output {}: got {} reference {} abs {:.3e} rel {:.3e}",
+            i, j, g, r, abs, rel
+        );
+    }
+    failures.is_empty()
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let (spec_path, input_path) = match (flag_value(&args, "--spec"), flag_value(&args, "--input")) {
+        (Some(spec), Some(input)) => (spec, input),
+        _ => usage(),
+    };
+
+    let default_tol = Tolerance {
+        atol: flag_value(&args, "--atol").map(str::parse).transpose()?.unwrap_or(1e-3),
+        rtol: flag_value(&args, "--rtol").map(str::parse).transpose()?.unwrap_or(1e-3),
+    };
+    // --tol backend:atol:rtol 은 여러 번 줄 수 있음
+    let mut overrides = vec![];
+    for (i, arg) in args.iter().enumerate() {
+        if arg != "--tol" {
+            continue;
+        }
+        let spec = args.get(i + 1).ok_or("--tol needs backend:atol:rtol")?;
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts.len() != 3 {
+            return Err(format!("bad --tol {}, expected backend:atol:rtol", spec).into());
+        }
+        overrides.push((
+            parts[0].to_string(),
+            Tolerance {
+                atol: parts[1].parse()?,
+                rtol: parts[2].parse()?,
+            },
+        ));
+    }
+    let tolerance = |name: &str| {
+        overrides
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, t)| *t)
+            .unwrap_or(default_tol)
+    };
+
+    let spec = ModelSpec::load(spec_path)?;
+    let inputs = load_input(input_path)?;
+    if let Some(row) = inputs.iter().find(|row| row.len() != spec.input_size) {
+        return Err(format!("input row has {} values, spec {} expects {}", row.len(), spec.name, spec.input_size).into());
+    }
+
+    // 기준값: f64 forward, 비교용으로 고정소수점 forward 도 함께 검사
+    let reference: Vec<Vec<f64>> = inputs.iter().map(|x| spec.forward(x)).collect();
+    let fixed: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|x| {
+            let q: Vec<i64> = x.iter().map(|&v| spec.quantize(v)).collect();
+            spec.forward_fixed(&q).into_iter().map(|y| spec.dequantize(y)).collect()
+        })
+        .collect();
+
+    println!("spec {} ({} samples, layers {:?})", spec.name, inputs.len(), spec.layer_sizes());
+    let mut all_ok = compare("fixed", &fixed, &reference, tolerance("fixed"));
+
+    for name in ["kimchi", "risc0"] {
+        if let Some(path) = flag_value(&args, &format!("--{}", name)) {
+            let ok = match load_outputs(path) {
+                Ok(outputs) => compare(name, &outputs, &reference, tolerance(name)),
+                Err(e) => {
+                    println!("{:<10} FAIL {}", name, e);
+                    false
+                }
+            };
+            all_ok &= ok;
+        }
+    }
+
+    if let Some(path) = flag_value(&args, "--ezkl") {
+        let ok = match load_ezkl(path, spec.output_size()) {
+            Ok((outputs, ezkl_inputs)) => {
+                // ezkl 은 입력도 양자화하므로 같은 입력인지 먼저 확인
+                let flat: Vec<f64> = inputs.iter().flatten().copied().collect();
+                let inputs_ok = compare("ezkl_in", &ezkl_inputs, &[flat], tolerance("ezkl"));
+                compare("ezkl", &outputs, &reference, tolerance("ezkl")) && inputs_ok
+            }
+            Err(e) => {
+                println!("{:<10} FAIL {}", "ezkl", e);
+                false
+            }
+        };
+        all_ok &= ok;
+    }
+
+    if !all_ok {
+        println!("backends disagree");
+        process::exit(1);
+    }
+    println!("all backends agree");
+    Ok(())
+}
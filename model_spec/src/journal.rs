@@ -0,0 +1,46 @@
+//! What the RISC Zero inference guest commits, shared by the guest and host.
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// SHA-256 digest, as raw bytes.
+pub type Hash = [u8; 32];
+
+/// Journal of one inference run. The hashes cover the model and input as the host sent them
+/// (the bytes of `binary` model and input files, or serialized words for the smartcore
+/// guest), so a receipt names what it was computed on.
+/// `T` is `f64` in float mode and `i64` in fixed mode.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InferenceJournal<T> {
+    pub model_hash: Hash,
+    pub input_hash: Hash,
+    /// Fixed-point scale of `outputs`; `None` when they are plain `f64`.
+    pub scale_bits: Option<u32>,
+    pub outputs: Vec<Vec<T>>,
+}
+
+impl InferenceJournal<i64> {
+    pub fn dequantize(&self) -> Vec<Vec<f64>> {
+        let scale = (1u64 << self.scale_bits.unwrap_or(0)) as f64;
+        self.outputs
+            .iter()
+            .map(|row| row.iter().map(|&y| y as f64 / scale).collect())
+            .collect()
+    }
+}
+
+/// Cycle counts the guest writes to its stdout after committing. They are not part
+/// of the proof; the host reads them to report per-sample cost.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CycleReport {
+    /// Cycles spent reading and preparing the model, before the first sample.
+    pub load_cycles: u64,
+    /// Cycles per input sample (read, deserialize and forward), in stream order.
+    pub sample_cycles: Vec<u64>,
+    /// Cycles of each layer's forward pass, summed over all samples, in layer order.
+    pub layer_cycles: Vec<u64>,
+}
+
+pub fn hex(hash: &Hash) -> String {
+    hash.iter().map(|b| alloc::format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,375 @@
+//! Backend-neutral MLP specification shared by every experiment.
+//!
+//! One JSON file describes layers, shapes, activations, fixed-point quantization
+//! and weights, so kimchi, RISC Zero and the Python/ezkl side all run the same
+//! network:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "name": "mlp_2_1_1",
+//!   "input_size": 2,
+//!   "quantization": { "scale_bits": 16 },
+//!   "layers": [
+//!     { "outputs": 1, "activation": "relu", "weights": [[1.0], [2.0]], "bias": [3.0] },
+//!     { "outputs": 1, "activation": "relu", "weights": [[0.3]], "bias": [0.0] }
+//!   ]
+//! }
+//! ```
+//!
+//! `weights[k][j]` connects input `k` to output `j` (inputs x outputs, the same
+//! layout as the old smartcore matrices and Orion's `j * outputs + i` indexing).
+//!
+//! The same spec can also be stored in a compact binary file with a header and checksum
+//! (`binary`), which `load` recognizes by its magic.
+//!
+//! Without the default `std` feature the crate is `no_std`: JSON loading/saving is left
+//! out, and what remains is enough for a guest to run a `FloatModel` or `QuantizedModel`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+pub mod binary;
+pub mod journal;
+pub mod tensor;
+
+use tensor::{Layer, Matrix};
+
+/// Spec format version this crate reads and writes.
+pub const SPEC_VERSION: u32 = 1;
+
+/// FP16x16, same as Orion's fixed-point type.
+pub const DEFAULT_SCALE_BITS: u32 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Activation {
+    Linear,
+    Relu,
+}
+
+impl Activation {
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            Activation::Linear => x,
+            Activation::Relu => x.max(0.0),
+        }
+    }
+
+    pub fn apply_fixed(self, x: i64) -> i64 {
+        match self {
+            Activation::Linear => x,
+            Activation::Relu => x.max(0),
+        }
+    }
+}
+
+impl fmt::Display for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activation::Linear => write!(f, "linear"),
+            Activation::Relu => write!(f, "relu"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Dense {
+    pub outputs: usize,
+    pub activation: Activation,
+    /// `inputs` rows of `outputs` values.
+    pub weights: Vec<Vec<f64>>,
+    pub bias: Vec<f64>,
+}
+
+impl Dense {
+    pub fn inputs(&self) -> usize {
+        self.weights.len()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quantization {
+    /// Values are stored as `round(x * 2^scale_bits)`.
+    pub scale_bits: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelSpec {
+    pub version: u32,
+    pub name: String,
+    pub input_size: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<Quantization>,
+    pub layers: Vec<Dense>,
+}
+
+#[derive(Debug)]
+pub enum SpecError {
+    Io(String),
+    Parse(String),
+    Version(u32),
+    Shape(String),
+    /// Bad binary file: magic, version, length or checksum.
+    Format(String),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::Io(e) => write!(f, "io error: {}", e),
+            SpecError::Parse(e) => write!(f, "malformed spec: {}", e),
+            SpecError::Version(v) => write!(f, "unsupported spec version {} (expected {})", v, SPEC_VERSION),
+            SpecError::Shape(e) => write!(f, "shape mismatch: {}", e),
+            SpecError::Format(e) => write!(f, "malformed binary file: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpecError {}
+
+impl ModelSpec {
+    #[cfg(feature = "std")]
+    pub fn from_json(text: &str) -> Result<Self, SpecError> {
+        let spec: ModelSpec = serde_json::from_str(text).map_err(|e| SpecError::Parse(e.to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Loads a JSON spec, or a binary model file (see `binary`) if the file starts with its magic.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SpecError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))?;
+        if bytes.starts_with(&binary::MAGIC) {
+            return Self::from_bytes(&bytes);
+        }
+        let text = String::from_utf8(bytes).map_err(|e| SpecError::Parse(e.to_string()))?;
+        Self::from_json(&text)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SpecError> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json()).map_err(|e| SpecError::Io(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Checks the version and that every layer's shape matches the previous one.
+    pub fn validate(&self) -> Result<(), SpecError> {
+        if self.version != SPEC_VERSION {
+            return Err(SpecError::Version(self.version));
+        }
+        if self.layers.is_empty() {
+            return Err(SpecError::Shape("model has no layers".to_string()));
+        }
+        let mut width = self.input_size;
+        for (i, layer) in self.layers.iter().enumerate() {
+            if layer.inputs() != width {
+                return Err(SpecError::Shape(format!(
+                    "layer {} has {} weight rows, previous layer outputs {}",
+                    i,
+                    layer.inputs(),
+                    width
+                )));
+            }
+            if layer.weights.iter().any(|row| row.len() != layer.outputs) || layer.bias.len() != layer.outputs {
+                return Err(SpecError::Shape(format!(
+                    "layer {} weights/bias are not {} wide",
+                    i, layer.outputs
+                )));
+            }
+            width = layer.outputs;
+        }
+        Ok(())
+    }
+
+    /// Input width followed by each layer's output width.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![self.input_size];
+        sizes.extend(self.layers.iter().map(|l| l.outputs));
+        sizes
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.layers.last().map(|l| l.outputs).unwrap_or(self.input_size)
+    }
+
+    pub fn scale_bits(&self) -> u32 {
+        self.quantization.map(|q| q.scale_bits).unwrap_or(DEFAULT_SCALE_BITS)
+    }
+
+    pub fn quantize(&self, x: f64) -> i64 {
+        quantize(x, self.scale_bits())
+    }
+
+    pub fn dequantize(&self, x: i64) -> f64 {
+        x as f64 / (1u64 << self.scale_bits()) as f64
+    }
+
+    /// Same weights in flat `tensor` form; assumes the spec is valid.
+    pub fn float_model(&self) -> FloatModel {
+        FloatModel {
+            input_size: self.input_size,
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| Layer {
+                    activation: layer.activation,
+                    weights: Matrix::from_rows(&layer.weights).expect("ragged weights"),
+                    bias: layer.bias.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Reference f64 forward pass for one sample. Panics if `input` is not `input_size` wide.
+    pub fn forward(&self, input: &[f64]) -> Vec<f64> {
+        assert!(
+            input.len() == self.input_size,
+            "input has {} values, model expects {}",
+            input.len(),
+            self.input_size
+        );
+        let mut x = input.to_vec();
+        for layer in &self.layers {
+            x = (0..layer.outputs)
+                .map(|j| {
+                    let sum = layer.bias[j]
+                        + x.iter().zip(&layer.weights).map(|(xk, row)| xk * row[j]).sum::<f64>();
+                    layer.activation.apply(sum)
+                })
+                .collect();
+        }
+        x
+    }
+
+    #[cfg(feature = "std")]
+    /// Weights and biases rounded to the spec's fixed-point scale.
+    pub fn quantized(&self) -> QuantizedModel {
+        QuantizedModel {
+            scale_bits: self.scale_bits(),
+            input_size: self.input_size,
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| Layer {
+                    activation: layer.activation,
+                    weights: Matrix {
+                        rows: layer.inputs(),
+                        cols: layer.outputs,
+                        data: layer.weights.iter().flatten().map(|&w| self.quantize(w)).collect(),
+                    },
+                    bias: layer.bias.iter().map(|&b| self.quantize(b)).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Fixed-point forward pass, bit-exact with the kimchi circuit (see `QuantizedModel::forward`).
+    pub fn forward_fixed(&self, input: &[i64]) -> Vec<i64> {
+        self.quantized().forward(input)
+    }
+}
+
+/// How the RISC Zero guest runs inference: soft-emulated `f64` or integer fixed point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InferenceMode {
+    Float,
+    Fixed,
+}
+
+impl core::str::FromStr for InferenceMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "float" | "f64" => Ok(InferenceMode::Float),
+            "fixed" => Ok(InferenceMode::Fixed),
+            _ => Err(format!("unknown inference mode {} (float or fixed)", s)),
+        }
+    }
+}
+
+impl fmt::Display for InferenceMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferenceMode::Float => write!(f, "float"),
+            InferenceMode::Fixed => write!(f, "fixed"),
+        }
+    }
+}
+
+/// `ModelSpec` weights in flat `tensor` form, for a guest running `f64` inference.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FloatModel {
+    pub input_size: usize,
+    pub layers: Vec<Layer<f64>>,
+}
+
+impl FloatModel {
+    pub fn forward(&self, input: &[f64]) -> Vec<f64> {
+        let mut x = input.to_vec();
+        for layer in &self.layers {
+            x = layer.forward(&x);
+        }
+        x
+    }
+}
+
+/// Integer-only form of a `ModelSpec`, so a guest can run it without any float ops.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedModel {
+    pub scale_bits: u32,
+    pub input_size: usize,
+    pub layers: Vec<Layer<i64>>,
+}
+
+/// `round(x * 2^scale_bits)`, halves away from zero like `f64::round`. Written without
+/// `f64::round` so a `no_std` guest quantizes exactly as the host does.
+pub fn quantize(x: f64, scale_bits: u32) -> i64 {
+    let y = x * (1u64 << scale_bits) as f64;
+    // 정수 부분으로 자른 뒤 남은 소수부가 0.5 이상이면 0에서 멀어지는 쪽으로 올림
+    let truncated = y as i64;
+    let fraction = y - truncated as f64;
+    if fraction >= 0.5 {
+        truncated.saturating_add(1)
+    } else if fraction <= -0.5 {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+impl QuantizedModel {
+    pub fn quantize(&self, x: f64) -> i64 {
+        quantize(x, self.scale_bits)
+    }
+
+    /// Per neuron `acc = sum(x_k * w_kj) + b_j * 2^s`, then `floor(acc / 2^s)` and the activation.
+    pub fn forward(&self, input: &[i64]) -> Vec<i64> {
+        let mut x = input.to_vec();
+        for layer in &self.layers {
+            x = layer.forward(&x, self.scale_bits);
+        }
+        x
+    }
+}
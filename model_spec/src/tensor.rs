@@ -0,0 +1,103 @@
+//! Flat row-major dense tensors, used by the RISC Zero guest instead of smartcore.
+//!
+//! Only `core` and `alloc` are needed, so a `no_std` guest can use this module. A `Matrix`
+//! serializes as its two dimensions plus one flat vector, rather than one length-prefixed
+//! vector per row, which keeps the words the guest reads (and hashes) compact.
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::Activation;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Matrix<T> {
+    pub rows: usize,
+    pub cols: usize,
+    /// `rows * cols` values, row after row.
+    pub data: Vec<T>,
+}
+
+impl<T: Copy> Matrix<T> {
+    /// `None` if the rows are not all the same length.
+    pub fn from_rows(rows: &[Vec<T>]) -> Option<Self> {
+        let cols = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != cols) {
+            return None;
+        }
+        Some(Matrix {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        })
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.data[i * self.cols + j]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        self.data[i * self.cols + j] = value;
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        (0..self.rows).map(|i| self.row(i).to_vec()).collect()
+    }
+}
+
+/// Dense layer with `weights` as inputs x outputs, like `Dense`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Layer<T> {
+    pub activation: Activation,
+    pub weights: Matrix<T>,
+    pub bias: Vec<T>,
+}
+
+impl<T> Layer<T> {
+    pub fn inputs(&self) -> usize {
+        self.weights.rows
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.weights.cols
+    }
+}
+
+// 짧은 입력은 항이 빠진 채로 계산되고 긴 입력은 weights.row(k) 에서 패닉하므로 먼저 확인
+fn assert_width(got: usize, inputs: usize) {
+    assert!(got == inputs, "input has {} values, layer expects {}", got, inputs);
+}
+
+impl Layer<f64> {
+    /// `y_j = act(b_j + sum_k x_k * w_kj)`, accumulated one weight row at a time so the
+    /// inner loop walks contiguous memory. Panics if `x` is not `inputs()` wide.
+    pub fn forward(&self, x: &[f64]) -> Vec<f64> {
+        assert_width(x.len(), self.inputs());
+        let mut y = self.bias.clone();
+        for (k, &xk) in x.iter().enumerate() {
+            for (yj, &w) in y.iter_mut().zip(self.weights.row(k)) {
+                *yj += xk * w;
+            }
+        }
+        y.into_iter().map(|v| self.activation.apply(v)).collect()
+    }
+}
+
+impl Layer<i64> {
+    /// Fixed-point version: `acc_j = b_j * 2^s + sum_k x_k * w_kj` in i128, then
+    /// `floor(acc_j / 2^s)` and the activation. Panics if `x` is not `inputs()` wide.
+    pub fn forward(&self, x: &[i64], scale_bits: u32) -> Vec<i64> {
+        assert_width(x.len(), self.inputs());
+        let mut acc: Vec<i128> = self.bias.iter().map(|&b| (b as i128) << scale_bits).collect();
+        for (k, &xk) in x.iter().enumerate() {
+            for (a, &w) in acc.iter_mut().zip(self.weights.row(k)) {
+                *a += xk as i128 * w as i128;
+            }
+        }
+        acc.into_iter()
+            .map(|a| self.activation.apply_fixed((a >> scale_bits) as i64))
+            .collect()
+    }
+}
@@ -0,0 +1,59 @@
+"""PyTorch <-> model spec (see `model_spec/src/lib.rs`) so ezkl runs the same network.
+
+    spec = torch_to_spec([*model.layers, model.output], ["relu"] * depth + ["linear"], "mlp5")
+    save_spec(spec, "mlp5/spec.json")
+"""
+import json
+
+import torch
+import torch.nn as nn
+
+SPEC_VERSION = 1
+DEFAULT_SCALE_BITS = 16
+
+
+def torch_to_spec(linears, activations, name, scale_bits=DEFAULT_SCALE_BITS):
+    layers = []
+    for linear, activation in zip(linears, activations):
+        # nn.Linear stores (out, in); the spec stores inputs x outputs
+        weight = linear.weight.detach().cpu().numpy()
+        layers.append({
+            "outputs": weight.shape[0],
+            "activation": activation,
+            "weights": weight.T.tolist(),
+            "bias": linear.bias.detach().cpu().numpy().tolist(),
+        })
+    return {
+        "version": SPEC_VERSION,
+        "name": name,
+        "input_size": linears[0].in_features,
+        "quantization": {"scale_bits": scale_bits},
+        "layers": layers,
+    }
+
+
+def spec_to_torch(spec):
+    if spec["version"] != SPEC_VERSION:
+        raise ValueError(f"unsupported spec version {spec['version']}")
+    modules = []
+    width = spec["input_size"]
+    for layer in spec["layers"]:
+        linear = nn.Linear(width, layer["outputs"])
+        with torch.no_grad():
+            linear.weight.copy_(torch.tensor(layer["weights"], dtype=torch.float32).T)
+            linear.bias.copy_(torch.tensor(layer["bias"], dtype=torch.float32))
+        modules.append(linear)
+        if layer["activation"] == "relu":
+            modules.append(nn.ReLU())
+        width = layer["outputs"]
+    return nn.Sequential(*modules)
+
+
+def save_spec(spec, path):
+    with open(path, "w") as f:
+        json.dump(spec, f, indent=2)
+
+
+def load_spec(path):
+    with open(path) as f:
+        return json.load(f)
@@ -43,6 +43,9 @@ run_benchmark() {
     echo "${framework},mlp,$exp_num,$proving_time,$memory_usage,$cpu_usage" >> $output_csv
 }
 
+# RISC Zero 집계 드라이버는 미리 빌드 (빌드 시간이 측정에 섞이지 않도록)
+(cd mlp_risczero && cargo build --release --bin aggregate)
+
 # MLP 모델에 대한 실험 루프
 # for i in {1..5}; do
 
@@ -52,6 +55,8 @@ for i in {1..12}; do
     # run_benchmark "orion" $i "scarb run --path models/linear_regression/orion"
     # run_benchmark "orion" $i "jupyter nbconvert --to notebook --execute ./models/mlp/orion/orion.ipynb --output orion_output"
     # run_benchmark "risczero" $i "cd mlp_risczero && ./zkvm"
+    # 영수증 i 개를 env::verify 로 합성, 세부 시간은 backend_results.csv 에 추가됨
+    run_benchmark "risczero" $i "./target/release/aggregate --count $i --out ../backend_results.csv"
 done
 
 echo "Experiment completed. Results saved to $output_csv."
@@ -0,0 +1,128 @@
+//! Client authentication with Schnorr signatures over Pallas (mina-signer).
+//!
+//! A client signs `(proof digest, exp, public output, nonce)` with its key, the
+//! server checks the signature against a local allowlist of public keys.
+use ark_ff::{BigInteger, PrimeField};
+use mina_hasher::{Hashable, ROInput};
+use mina_signer::{BaseField, Keypair, NetworkId, PubKey, ScalarField, Signature, Signer};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::protocol::SignatureBytes;
+
+pub const NETWORK: NetworkId = NetworkId::TESTNET;
+
+/// 서명 대상 메시지
+#[derive(Clone)]
+pub struct Submission {
+    pub proof_digest: [u8; 32],
+    pub exp_num: u32,
+    pub public_output: u128,
+    pub nonce: u64,
+}
+
+impl Submission {
+    pub fn new(proof_bytes: &[u8], exp_num: u32, public_output: u128, nonce: u64) -> Self {
+        Submission {
+            proof_digest: Sha256::digest(proof_bytes).into(),
+            exp_num,
+            public_output,
+            nonce,
+        }
+    }
+}
+
+impl Hashable for Submission {
+    type D = NetworkId;
+
+    fn to_roinput(&self) -> ROInput {
+        ROInput::new()
+            .append_bytes(&self.proof_digest)
+            .append_u32(self.exp_num)
+            .append_bytes(&self.public_output.to_le_bytes())
+            .append_u64(self.nonce)
+    }
+
+    fn domain_string(network_id: NetworkId) -> Option<String> {
+        match network_id {
+            NetworkId::MAINNET => "ZkmlSubmitMainnet",
+            NetworkId::TESTNET => "ZkmlSubmitTestnet",
+        }
+        .to_string()
+        .into()
+    }
+}
+
+pub fn sign_submission(keypair: &Keypair, submission: &Submission) -> SignatureBytes {
+    let mut signer = mina_signer::create_kimchi::<Submission>(NETWORK);
+    let sig = signer.sign(keypair, submission);
+
+    let mut rx = [0u8; 32];
+    let mut s = [0u8; 32];
+    rx.copy_from_slice(&sig.rx.into_repr().to_bytes_le());
+    s.copy_from_slice(&sig.s.into_repr().to_bytes_le());
+    SignatureBytes { rx, s }
+}
+
+pub fn verify_submission(pub_key: &PubKey, submission: &Submission, sig: &SignatureBytes) -> bool {
+    let sig = Signature::new(
+        BaseField::from_le_bytes_mod_order(&sig.rx),
+        ScalarField::from_le_bytes_mod_order(&sig.s),
+    );
+    let mut signer = mina_signer::create_kimchi::<Submission>(NETWORK);
+    signer.verify(&sig, pub_key, submission)
+}
+
+/// 클라이언트 비밀키 (hex) 로드
+pub fn load_keypair(path: impl AsRef<Path>) -> Result<Keypair, Box<dyn Error>> {
+    let path = path.as_ref();
+    let hex = fs::read_to_string(path)
+        .map_err(|e| format!("can't read key {}: {}", path.display(), e))?;
+    Keypair::from_hex(hex.trim()).map_err(|e| format!("bad key {}: {:?}", path.display(), e).into())
+}
+
+/// Allowlist of registered clients: one `<name> <address>` per line, `#` for comments.
+pub struct Allowlist {
+    by_address: BTreeMap<String, (String, PubKey)>,
+}
+
+impl Allowlist {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("can't read allowlist {}: {}", path.display(), e))?;
+
+        let mut by_address = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (name, address) = match (parts.next(), parts.next()) {
+                (Some(name), Some(address)) => (name, address),
+                _ => return Err(format!("{}:{}: expected `<name> <address>`", path.display(), i + 1).into()),
+            };
+            let pub_key = PubKey::from_address(address)
+                .map_err(|e| format!("{}:{}: bad address: {:?}", path.display(), i + 1, e))?;
+            by_address.insert(address.to_string(), (name.to_string(), pub_key));
+        }
+        Ok(Allowlist { by_address })
+    }
+
+    /// Registered name and key for `address`, if any.
+    pub fn lookup(&self, address: &str) -> Option<(&str, &PubKey)> {
+        self.by_address.get(address).map(|(name, key)| (name.as_str(), key))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.by_address.values().map(|(name, _)| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_address.len()
+    }
+}
@@ -1,40 +1,15 @@
-use ark_ff::PrimeField;
 use kimchi::mina_curves::pasta::Fp;
 use tokio::net::TcpStream;
-use tokio::io::AsyncWriteExt;
-use serde::{Deserialize, Serialize};
-use bincode;
 use std::env;
 use std::error::Error;
+use std::time::Instant;
 
-mod mlp;
+use recursion_exp::{auth, framing, mlp, protocol, secure};
 
-use mlp::mlp_by_depth;
-
-#[derive(Serialize, Deserialize)]
-struct NumberRequest {
-    message: String,
-}
-
-#[derive(Serialize, Deserialize)]
-struct NumberResponse {
-    public_output: u128,
-    msm_call_count: u64,
-    msm_accumulated_time: f32,
-}
-
-pub fn fp_to_integer(fp: Fp) -> u128 {
-    // Fp의 내부 표현을 BigInteger256으로 가져옵니다.
-    let big_integer = fp.into_repr();  // Fp에서 BigInteger256로 변환
-    
-    // BigInteger256은 4개의 u64 배열로 구성되어 있으므로, 이를 바이트로 변환
-    let mut result = 0u128;
-    for &part in big_integer.0.iter().rev() {
-        result = (result << 64) | part as u128;
-    }
-
-    result // 결과를 문자열로 변환하여 반환
-}
+use mlp::{fp_to_integer, mlp_by_depth, MlpProof};
+use framing::FrameConfig;
+use protocol::{NumberRequest, NumberResponse, SubmissionAck};
+use secure::{NoiseKeys, SecureChannel};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -50,9 +25,16 @@ async fn main() -> Result<(), Box<dyn Error>> {
         "127.0.0.1:4000".to_string()
     };
 
-    let mut socket = TcpStream::connect(server_addr).await?;
+    let socket = TcpStream::connect(server_addr).await?;
     println!("Connected to server");
 
+    // NOISE_KEY가 설정되어 있으면 Noise XX 핸드셰이크
+    let frame_config = FrameConfig::from_env();
+    let noise_keys = NoiseKeys::from_env()?;
+    let mut channel = SecureChannel::connect(socket, noise_keys.as_ref(), frame_config).await?;
+    println!("transport: {}", if channel.is_encrypted() { "noise" } else { "plain" });
+    println!("handshake_time: {:?}", channel.stats.handshake.as_secs_f32());
+
     let args: Vec<String> = env::args().collect();
     let exp: usize = if args.len() > 2 {
         args[2].parse().unwrap_or(1)  // 명령줄 인수가 있으면 그 값을 사용, 없으면 기본값 1
@@ -60,12 +42,28 @@ async fn main() -> Result<(), Box<dyn Error>> {
         1
     };
 
-    let result: (kimchi::proof::ProverProof<ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>, kimchi::poly_commitment::evaluation_proof::OpeningProof<ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>>>, Vec<Fp>) = mlp_by_depth(exp);
+    // 제출 서명에 사용할 클라이언트 키
+    let key_path = if args.len() > 3 {
+        args[3].clone()
+    } else {
+        "keys/client.key".to_string()
+    };
+    let keypair = auth::load_keypair(&key_path)?;
+
+    // 서버가 발급한 nonce 수신
+    let number_request: NumberRequest = channel.recv().await?;
+    println!("Received nonce: {} ({})", number_request.nonce, number_request.message);
+    let nonce = number_request.nonce;
+
+    // nonce를 추가 공개 입력으로 넣어 증명 생성
+    let result: (MlpProof, Vec<Fp>, Vec<Fp>) = mlp_by_depth(exp, nonce);
 
-    let _proof: kimchi::poly_commitment::evaluation_proof::OpeningProof<ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>> = result.0.proof;
-    let public_output: Vec<Fp> = result.1;
+    // 서버가 검증/저장할 수 있도록 opening proof만이 아니라 전체 ProverProof를 보냄
+    let _proof: MlpProof = result.0;
+    let public_input: Vec<Fp> = result.1;
+    let public_output: Vec<Fp> = result.2;
 
-    println!("_proof: {:?}", _proof);
+    println!("_proof: {:?}", _proof.proof);
     println!("public_output: {:?}", public_output);
     let public_output_u = fp_to_integer(public_output[0]);
     println!("public_output_u: {:?}", public_output_u);
@@ -84,25 +82,33 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // let number_request = NumberRequest {
     //     message: "Please send your number".to_string(),
     // };
-    let request_data = bincode::serialize(&_proof).unwrap();
-    let data_length = request_data.len() as u32;
+    let request_data = framing::encode(&_proof)?;
 
-    // 데이터 길이 전송
-    socket.write_all(&data_length.to_be_bytes()).await?;
+    // 데이터 전송 (4바이트 길이 + 데이터), 서버 응답까지를 제출 지연 시간으로 측정
+    let submit_start = Instant::now();
+    let crypto_before = channel.stats.crypto;
+    channel.write_frame(&request_data).await?;
 
-    // 데이터 전송
-    socket.write_all(&request_data).await?;
+    // 증명 digest, 공개 출력, nonce에 서명
+    let submission = auth::Submission::new(&request_data, exp as u32, public_output_u, nonce);
+    let signature = auth::sign_submission(&keypair, &submission);
 
     let number_response = NumberResponse { 
         public_output: public_output_u,
         msm_call_count: msm_count,
         msm_accumulated_time: msm_time.as_secs_f32(),
+        exp_num: exp as u32,
+        public_input: public_input.iter().map(|x| fp_to_integer(*x)).collect(),
+        nonce,
+        public_key: keypair.public.into_address(),
+        signature,
     };
-    let response_data = bincode::serialize(&number_response).unwrap();
-    let data_length = response_data.len() as u32;
+    channel.send(&number_response).await?;
 
-    socket.write_all(&data_length.to_be_bytes()).await?;
-    socket.write_all(&response_data).await?;
+    let ack: SubmissionAck = channel.recv().await?;
+    println!("submission_accepted: {} ({})", ack.accepted, ack.message);
+    println!("submit_time: {:?}", submit_start.elapsed().as_secs_f32());
+    println!("crypto_time: {:?}", (channel.stats.crypto - crypto_before).as_secs_f32());
 
     // let request_data = bincode::serialize(&_proof).unwrap();
 
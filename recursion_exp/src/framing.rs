@@ -0,0 +1,266 @@
+//! Length-prefixed framing (4 byte big-endian length + bincode body) with size
+//! limits and timeouts, so an untrusted peer can't make us allocate or wait
+//! without bound.
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::timeout;
+
+#[derive(Clone, Debug)]
+pub struct FrameConfig {
+    /// Largest frame body we accept, in bytes.
+    pub max_frame_len: usize,
+    /// How long to wait for the next frame to start (covers client proving time).
+    pub idle_timeout: Duration,
+    /// How long the rest of a frame may take once its length has arrived.
+    pub read_timeout: Duration,
+}
+
+impl Default for FrameConfig {
+    fn default() -> Self {
+        FrameConfig {
+            max_frame_len: 16 << 20,
+            idle_timeout: Duration::from_secs(3600),
+            read_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl FrameConfig {
+    /// Defaults overridden by `MAX_FRAME_BYTES`, `IDLE_TIMEOUT_SECS`, `READ_TIMEOUT_SECS`.
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            env::var(name).ok().and_then(|s| s.parse().ok())
+        }
+
+        let default = FrameConfig::default();
+        FrameConfig {
+            max_frame_len: var("MAX_FRAME_BYTES").unwrap_or(default.max_frame_len),
+            idle_timeout: var("IDLE_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.idle_timeout),
+            read_timeout: var("READ_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.read_timeout),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FrameError {
+    Io(io::Error),
+    /// The peer closed the connection cleanly, before the next frame started.
+    Closed,
+    /// The peer closed the connection in the middle of a frame.
+    Truncated,
+    Timeout,
+    TooLarge { len: usize, max: usize },
+    Decode(bincode::Error),
+    /// Noise handshake or decryption failure.
+    Noise(String),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Io(e) => write!(f, "io error: {}", e),
+            FrameError::Closed => write!(f, "connection closed by peer"),
+            FrameError::Truncated => write!(f, "connection closed mid-frame"),
+            FrameError::Timeout => write!(f, "timed out waiting for frame"),
+            FrameError::TooLarge { len, max } => {
+                write!(f, "frame of {} bytes exceeds limit of {} bytes", len, max)
+            }
+            FrameError::Decode(e) => write!(f, "malformed frame: {}", e),
+            FrameError::Noise(e) => write!(f, "noise error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+impl From<io::Error> for FrameError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            FrameError::Truncated
+        } else {
+            FrameError::Io(e)
+        }
+    }
+}
+
+impl From<bincode::Error> for FrameError {
+    fn from(e: bincode::Error) -> Self {
+        FrameError::Decode(e)
+    }
+}
+
+// bincode::serialize 와 같은 인코딩 (fixint), 크기 제한 + 남는 바이트 거부
+fn bincode_options(limit: usize) -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_limit(limit as u64)
+}
+
+// 길이 prefix 읽기: 한 바이트도 오기 전의 EOF 는 정상 종료 (Closed), 도중의 EOF 는 Truncated
+async fn read_length<R: AsyncRead + Unpin>(reader: &mut R) -> Result<[u8; 4], FrameError> {
+    let mut length_buffer = [0u8; 4];
+    let mut filled = 0;
+    while filled < length_buffer.len() {
+        match reader.read(&mut length_buffer[filled..]).await? {
+            0 if filled == 0 => return Err(FrameError::Closed),
+            0 => return Err(FrameError::Truncated),
+            n => filled += n,
+        }
+    }
+    Ok(length_buffer)
+}
+
+pub async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    config: &FrameConfig,
+) -> Result<Vec<u8>, FrameError> {
+    let length_buffer = timeout(config.idle_timeout, read_length(reader))
+        .await
+        .map_err(|_| FrameError::Timeout)??;
+
+    let len = u32::from_be_bytes(length_buffer) as usize;
+    if len > config.max_frame_len {
+        return Err(FrameError::TooLarge {
+            len,
+            max: config.max_frame_len,
+        });
+    }
+
+    let mut buffer = vec![0u8; len];
+    timeout(config.read_timeout, reader.read_exact(&mut buffer))
+        .await
+        .map_err(|_| FrameError::Timeout)??;
+    Ok(buffer)
+}
+
+pub async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    data: &[u8],
+) -> Result<(), FrameError> {
+    let len = u32::try_from(data.len()).map_err(|_| FrameError::TooLarge {
+        len: data.len(),
+        max: u32::MAX as usize,
+    })?;
+    writer.write_all(&len.to_be_bytes()).await?;
+    writer.write_all(data).await?;
+    Ok(())
+}
+
+/// Decodes a frame body, never reading past `max_frame_len` worth of data.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8], config: &FrameConfig) -> Result<T, FrameError> {
+    Ok(bincode_options(config.max_frame_len).deserialize(bytes)?)
+}
+
+pub fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, FrameError> {
+    Ok(bincode::serialize(value)?)
+}
+
+pub async fn recv<T: DeserializeOwned, R: AsyncRead + Unpin>(
+    reader: &mut R,
+    config: &FrameConfig,
+) -> Result<T, FrameError> {
+    let bytes = read_frame(reader, config).await?;
+    decode(&bytes, config)
+}
+
+pub async fn send<T: Serialize, W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    value: &T,
+) -> Result<(), FrameError> {
+    write_frame(writer, &encode(value)?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::NumberResponse;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use tokio::io::duplex;
+
+    fn config() -> FrameConfig {
+        FrameConfig {
+            max_frame_len: 1024,
+            idle_timeout: Duration::from_millis(200),
+            read_timeout: Duration::from_millis(50),
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let (mut a, mut b) = duplex(4096);
+        send(&mut a, &(7u32, "seven".to_string())).await.unwrap();
+        let value: (u32, String) = recv(&mut b, &config()).await.unwrap();
+        assert_eq!(value, (7, "seven".to_string()));
+    }
+
+    #[tokio::test]
+    async fn eof_before_prefix_is_closed() {
+        let (a, mut b) = duplex(64);
+        drop(a);
+        assert!(matches!(read_frame(&mut b, &config()).await, Err(FrameError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn short_prefix_is_truncated() {
+        let (mut a, mut b) = duplex(64);
+        a.write_all(&[0, 0]).await.unwrap();
+        drop(a);
+        assert!(matches!(read_frame(&mut b, &config()).await, Err(FrameError::Truncated)));
+    }
+
+    #[tokio::test]
+    async fn cut_off_body_is_truncated() {
+        let (mut a, mut b) = duplex(64);
+        a.write_all(&10u32.to_be_bytes()).await.unwrap();
+        a.write_all(&[1, 2, 3]).await.unwrap();
+        drop(a);
+        assert!(matches!(read_frame(&mut b, &config()).await, Err(FrameError::Truncated)));
+    }
+
+    #[tokio::test]
+    async fn oversized_prefix_is_rejected_before_reading_body() {
+        // 본문 없이 길이만 보내도 바로 TooLarge: 본문을 기다리거나 버퍼를 잡지 않음
+        let (mut a, mut b) = duplex(64);
+        a.write_all(&u32::MAX.to_be_bytes()).await.unwrap();
+        match read_frame(&mut b, &config()).await {
+            Err(FrameError::TooLarge { len, max }) => {
+                assert_eq!(len, u32::MAX as usize);
+                assert_eq!(max, 1024);
+            }
+            other => panic!("expected TooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn garbage_is_a_decode_error() {
+        let mut rng = StdRng::seed_from_u64(29);
+        for len in [0, 1, 7, 64, 300, 1024] {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let result = decode::<NumberResponse>(&bytes, &config());
+            assert!(matches!(result, Err(FrameError::Decode(_))), "{} random bytes decoded", len);
+        }
+    }
+
+    #[tokio::test]
+    async fn silent_peer_times_out() {
+        let (a, mut b) = duplex(64);
+        assert!(matches!(read_frame(&mut b, &config()).await, Err(FrameError::Timeout)));
+
+        // 길이만 보내고 멈춘 경우는 read_timeout
+        let (mut c, mut d) = duplex(64);
+        c.write_all(&8u32.to_be_bytes()).await.unwrap();
+        assert!(matches!(read_frame(&mut d, &config()).await, Err(FrameError::Timeout)));
+        drop((a, c));
+    }
+}
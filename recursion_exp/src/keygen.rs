@@ -0,0 +1,50 @@
+use mina_signer::Keypair;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use recursion_exp::secure;
+
+fn usage() -> ! {
+    eprintln!("usage: keygen <name> [dir]          client signing key");
+    eprintln!("       keygen --noise <name> [dir]  Noise static keypair");
+    std::process::exit(2);
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), Box<dyn Error>> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+    fs::write(path, contents)?;
+    eprintln!("wrote {}", path.display());
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let noise = args.len() > 1 && args[1] == "--noise";
+    let rest: Vec<&String> = args.iter().skip(if noise { 2 } else { 1 }).collect();
+    if rest.is_empty() {
+        usage();
+    }
+    let name = rest[0];
+    let dir = if rest.len() > 1 { rest[1].clone() } else { "keys".to_string() };
+    fs::create_dir_all(&dir)?;
+
+    if noise {
+        // Noise 정적 키: NOISE_KEY=<name>.noise.key, 상대방은 NOISE_REMOTE_PUB=<name>.noise.pub
+        let (private, public) = secure::generate_keypair()?;
+        write_new(&Path::new(&dir).join(format!("{}.noise.key", name)), &hex::encode(private))?;
+        write_new(&Path::new(&dir).join(format!("{}.noise.pub", name)), &hex::encode(public))?;
+        return Ok(());
+    }
+
+    // 클라이언트 서명키 생성: keys/<name>.key 에 비밀키(hex)를 쓰고 allowlist 줄을 출력
+    let keypair = Keypair::rand(&mut rand::rngs::OsRng).map_err(|e| format!("{:?}", e))?;
+    write_new(&Path::new(&dir).join(format!("{}.key", name)), &keypair.to_hex())?;
+
+    eprintln!("add this line to the server allowlist:");
+    println!("{} {}", name, keypair.public.into_address());
+    Ok(())
+}
@@ -0,0 +1,8 @@
+//! Modules shared by the server, client and tool binaries.
+pub mod auth;
+pub mod framing;
+pub mod mlp;
+pub mod nonce;
+pub mod protocol;
+pub mod secure;
+pub mod store;
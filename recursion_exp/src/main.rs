@@ -17,7 +17,7 @@ use kimchi::poly_commitment::{commitment::CommitmentCurve, evaluation_proof::Ope
 use std::array;
 use std::time::Instant;
 
-mod mlp;
+use recursion_exp::mlp;
 
 use std::env;
 
@@ -4,22 +4,34 @@ use kimchi::{
     circuits::{
         wires::COLUMNS,
     },
+    error::VerifyError,
     proof::ProverProof,
-    prover_index::testing::new_index_for_test,
+    prover_index::{testing::new_index_for_test, ProverIndex},
+    verifier::verify,
+    verifier_index::VerifierIndex,
 };
-use ark_ff::Zero;
+use ark_ff::{PrimeField, Zero};
 use kimchi::groupmap::GroupMap;
 use kimchi::mina_curves::pasta::{Fp, Vesta, VestaParameters};
 use kimchi::mina_poseidon::{
     constants::PlonkSpongeConstantsKimchi,
     sponge::{DefaultFqSponge, DefaultFrSponge},
 };
-use kimchi::poly_commitment::{commitment::CommitmentCurve};
+use kimchi::poly_commitment::{commitment::CommitmentCurve, evaluation_proof::OpeningProof};
 
 type SpongeParams = PlonkSpongeConstantsKimchi;
 type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
 type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
 
+pub type MlpProof = ProverProof<Vesta, OpeningProof<Vesta>>;
+pub type MlpIndex = ProverIndex<Vesta, OpeningProof<Vesta>>;
+pub type MlpVerifierIndex = VerifierIndex<Vesta, OpeningProof<Vesta>>;
+
+// Input size: 4
+pub const INPUT_SIZE: usize = 4;
+// 서버가 발급한 nonce가 입력 뒤에 추가 공개 입력으로 들어감
+pub const NONCE_INPUTS: usize = 1;
+
 
 use ark_ff::FftField;
 use kimchi::{
@@ -107,6 +119,20 @@ pub fn create_mlp_circuit(input_size: usize, depth: usize) -> Vec<CircuitGate<Fp
     gates
 }
 
+/// nonce를 추가 공개 입력으로 받는 MLP 서킷
+/// 공개 입력 row는 서킷 앞쪽에 있어야 하므로 입력 Pub 게이트 바로 뒤에 끼워 넣고 row를 다시 매김
+pub fn create_mlp_circuit_with_nonce(input_size: usize, depth: usize) -> Vec<CircuitGate<Fp>> {
+    let mut gates = create_mlp_circuit(input_size, depth);
+    gates.insert(
+        input_size,
+        CircuitGate::create_generic_gadget(Wire::for_row(input_size), GenericGateSpec::Pub, None),
+    );
+    for (r, gate) in gates.iter_mut().enumerate() {
+        gate.wires = Wire::for_row(r);
+    }
+    gates
+}
+
 /// Witness 생성 (depth를 받음)
 pub fn fill_in_mlp_witness<F: FftField>(
     start_row: usize,
@@ -153,26 +179,69 @@ pub fn fill_in_mlp_witness<F: FftField>(
     
 }
 
-pub fn mlp_by_depth(exp: usize) 
--> (ProverProof<ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>, 
-kimchi::poly_commitment::evaluation_proof::OpeningProof<ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>>>, Vec<Fp>) {
+pub fn fp_to_integer(fp: Fp) -> u128 {
+    // Fp의 내부 표현을 BigInteger256으로 가져옵니다.
+    let big_integer = fp.into_repr();  // Fp에서 BigInteger256로 변환
+    
+    // BigInteger256은 4개의 u64 배열로 구성되어 있으므로, 이를 바이트로 변환
+    let mut result = 0u128;
+    for &part in big_integer.0.iter().rev() {
+        result = (result << 64) | part as u128;
+    }
+
+    result // 결과를 문자열로 변환하여 반환
+}
+
+/// 실험에서 사용하는 공개 입력: 입력 4개 + nonce
+pub fn mlp_public_input(nonce: u64) -> Vec<Fp> {
+    let mut public = vec![Fp::from(3u8); INPUT_SIZE];
+    public.push(Fp::from(nonce));
+    public
+}
+
+/// exp에 해당하는 MLP 서킷의 prover index 생성 (depth = 2^exp)
+pub fn mlp_index(exp: usize, public_len: usize) -> MlpIndex {
+    let depth = 2usize.pow(exp.try_into().unwrap());
+    let gates = create_mlp_circuit_with_nonce(INPUT_SIZE, depth);
+    new_index_for_test(gates, public_len)
+}
+
+/// 서버/CLI 검증용 verifier index. 서킷이 결정적이므로 exp만으로 재구성 가능
+pub fn mlp_verifier_index(exp: usize) -> MlpVerifierIndex {
+    mlp_index(exp, INPUT_SIZE + NONCE_INPUTS).verifier_index()
+}
+
+pub fn verify_mlp_proof(
+    verifier_index: &MlpVerifierIndex,
+    proof: &MlpProof,
+    public: &[Fp],
+) -> Result<(), VerifyError> {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    verify::<Vesta, BaseSponge, ScalarSponge, OpeningProof<Vesta>>(
+        &group_map,
+        verifier_index,
+        proof,
+        public,
+    )
+}
+
+pub fn mlp_by_depth(exp: usize, nonce: u64) -> (MlpProof, Vec<Fp>, Vec<Fp>) {
 
     println!("run exp {}", exp);
 
     let depth = 2usize.pow(exp.try_into().unwrap());
     println!("Creating proof for depth: {}", depth);
 
-    // Input size: 4
-    let public = vec![Fp::from(3u8); 4];
+    let public = mlp_public_input(nonce);
     
     println!("public.len() {}", public.len());
-    let gates = create_mlp_circuit(public.len(), depth);
+    let gates = create_mlp_circuit_with_nonce(INPUT_SIZE, depth);
 
     // create witness
     let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); gates.len()]);
-    fill_in_mlp_witness(0, &mut witness, &public, public.len(), depth);
+    fill_in_mlp_witness(0, &mut witness, &public, INPUT_SIZE, depth);
 
-    let index = new_index_for_test(gates, public.len());
+    let index = mlp_index(exp, public.len());
     // let verifier_index = index.verifier_index();
 
     // let verifier_index_serialize =
@@ -195,5 +264,5 @@ kimchi::poly_commitment::evaluation_proof::OpeningProof<ark_ec::short_weierstras
     // println!("witness[0].len() - 1: {:?}", witness[0].len() - 1);
     let public_output: Vec<Fp> = vec![witness[0][0]];
     println!("public_output: {:?}", public_output);
-    (proof, public_output)
+    (proof, public, public_output)
 }
@@ -0,0 +1,77 @@
+//! Server-issued nonces for replay protection.
+//!
+//! Each connection gets a fresh nonce which the client puts into its proof as an
+//! extra public input. A nonce can be consumed once, and only within `ttl`.
+//!
+//! Issued and used nonces are both dropped once they are twice `ttl` old, so the registry
+//! stays bounded on a long-running server. A replay after that is still refused: the nonce
+//! is then reported as never issued instead of reused.
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq)]
+pub enum NonceError {
+    Unknown,
+    Reused,
+    Stale(Duration),
+}
+
+impl fmt::Display for NonceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonceError::Unknown => write!(f, "nonce was never issued"),
+            NonceError::Reused => write!(f, "nonce was already used"),
+            NonceError::Stale(age) => write!(f, "nonce expired ({:?} old)", age),
+        }
+    }
+}
+
+impl std::error::Error for NonceError {}
+
+pub struct NonceRegistry {
+    issued: HashMap<u64, Instant>,
+    /// Consumed nonces with the time they were issued, kept until they expire.
+    used: HashMap<u64, Instant>,
+    ttl: Duration,
+}
+
+impl NonceRegistry {
+    pub fn new(ttl: Duration) -> Self {
+        NonceRegistry {
+            issued: HashMap::new(),
+            used: HashMap::new(),
+            ttl,
+        }
+    }
+
+    pub fn issue(&mut self) -> u64 {
+        // 오래된 nonce 정리 (ttl 두 배까지는 남겨서 Stale/Reused 로 보고되게 함)
+        let keep = self.ttl * 2;
+        self.issued.retain(|_, at| at.elapsed() <= keep);
+        self.used.retain(|_, at| at.elapsed() <= keep);
+
+        loop {
+            let nonce: u64 = rand::random();
+            if !self.used.contains_key(&nonce) && !self.issued.contains_key(&nonce) {
+                self.issued.insert(nonce, Instant::now());
+                return nonce;
+            }
+        }
+    }
+
+    /// Marks `nonce` as used. Fails if it was never issued, already used or expired.
+    pub fn consume(&mut self, nonce: u64) -> Result<(), NonceError> {
+        if self.used.contains_key(&nonce) {
+            return Err(NonceError::Reused);
+        }
+        let issued_at = self.issued.remove(&nonce).ok_or(NonceError::Unknown)?;
+        self.used.insert(nonce, issued_at);
+
+        let age = issued_at.elapsed();
+        if age > self.ttl {
+            return Err(NonceError::Stale(age));
+        }
+        Ok(())
+    }
+}
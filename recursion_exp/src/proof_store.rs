@@ -0,0 +1,80 @@
+use kimchi::mina_curves::pasta::Fp;
+use std::env;
+use std::error::Error;
+use std::process;
+use std::time::Instant;
+
+use recursion_exp::{mlp, store};
+
+use mlp::MlpProof;
+use store::{ProofRecord, ProofStore, Verdict};
+
+fn usage() -> ! {
+    eprintln!("usage: proof_store <list | show <hash> | reverify <hash>>");
+    eprintln!("       store directory is taken from PROOF_STORE (default: store)");
+    process::exit(2);
+}
+
+// 해시 prefix로 레코드 하나를 찾음 (같은 증명이 여러 번 제출됐으면 마지막 것)
+fn find_one(store: &ProofStore, prefix: &str) -> Result<ProofRecord, Box<dyn Error>> {
+    let records = store.find(prefix)?;
+    let mut hashes: Vec<&str> = records.iter().map(|r| r.hash.as_str()).collect();
+    hashes.sort();
+    hashes.dedup();
+    match hashes.len() {
+        0 => Err(format!("no proof matching {}", prefix).into()),
+        1 => Ok(records.into_iter().last().unwrap()),
+        n => Err(format!("{} proofs match {}, use a longer prefix", n, prefix).into()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        usage();
+    }
+
+    let store_path = env::var("PROOF_STORE").unwrap_or_else(|_| "store".to_string());
+    let store = ProofStore::open(&store_path)?;
+
+    match args[1].as_str() {
+        "list" => {
+            println!("hash,received_at,circuit_id,client,public_output,proof_len,verdict");
+            for r in store.list()? {
+                println!(
+                    "{},{},{},{},{},{},{:?}",
+                    r.hash, r.received_at, r.circuit_id, r.client.addr, r.public_output, r.proof_len, r.verdict
+                );
+            }
+        }
+        "show" if args.len() > 2 => {
+            let record = find_one(&store, &args[2])?;
+            println!("{}", serde_json::to_string_pretty(&record)?);
+        }
+        "reverify" if args.len() > 2 => {
+            let record = find_one(&store, &args[2])?;
+            let proof_bytes = store.load_proof(&record.hash)?;
+            let proof: MlpProof = bincode::deserialize(&proof_bytes)?;
+            let public: Vec<Fp> = record.public_input.iter().map(|x| Fp::from(*x)).collect();
+
+            let verifier_index = mlp::mlp_verifier_index(record.exp_num as usize);
+            let start = Instant::now();
+            let verdict = match mlp::verify_mlp_proof(&verifier_index, &proof, &public) {
+                Ok(()) => Verdict::Valid,
+                Err(e) => Verdict::Invalid(format!("{:?}", e)),
+            };
+            println!("hash: {}", record.hash);
+            println!("stored verdict: {:?}", record.verdict);
+            println!("reverify verdict: {:?}", verdict);
+            println!("verify_time: {}", start.elapsed().as_secs_f32());
+
+            if verdict != record.verdict {
+                eprintln!("verdict differs from the stored one");
+                process::exit(1);
+            }
+        }
+        _ => usage(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+// 서버 -> 클라이언트 요청 메시지: 접속마다 새 nonce 발급
+#[derive(Serialize, Deserialize)]
+pub struct NumberRequest {
+    pub message: String,
+    pub nonce: u64,
+}
+
+// Pallas Schnorr 서명 (rx, s), 각각 little-endian 32바이트
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SignatureBytes {
+    pub rx: [u8; 32],
+    pub s: [u8; 32],
+}
+
+// 클라이언트 -> 서버: 증명 뒤에 보내는 메타데이터
+#[derive(Serialize, Deserialize)]
+pub struct NumberResponse {
+    pub public_output: u128,
+    pub msm_call_count: u64,
+    pub msm_accumulated_time: f32,
+    pub exp_num: u32,
+    pub public_input: Vec<u128>,
+    pub nonce: u64,
+    pub public_key: String,
+    pub signature: SignatureBytes,
+}
+
+// 서버 -> 클라이언트: 제출 처리 결과
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SubmissionAck {
+    pub accepted: bool,
+    pub message: String,
+}
@@ -0,0 +1,248 @@
+//! Optional Noise (XX) encrypted channel on top of the framing layer.
+//!
+//! Without keys the channel is plain framed TCP. With keys every logical frame is
+//! sent as an encrypted length header followed by encrypted chunks, each chunk
+//! fitting in one Noise message.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use snow::{Builder, HandshakeState, TransportState};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+use crate::framing::{self, FrameConfig, FrameError};
+
+const NOISE_PARAMS: &str = "Noise_XX_25519_ChaChaPoly_BLAKE2s";
+const MAX_NOISE_MSG: usize = 65535;
+const TAG_LEN: usize = 16;
+const MAX_CHUNK: usize = MAX_NOISE_MSG - TAG_LEN;
+
+/// Local static key and, optionally, the peer's static key to pin.
+pub struct NoiseKeys {
+    pub private: Vec<u8>,
+    pub remote_public: Option<Vec<u8>>,
+}
+
+fn load_hex(path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let text = fs::read_to_string(Path::new(path)).map_err(|e| format!("can't read {}: {}", path, e))?;
+    Ok(hex::decode(text.trim()).map_err(|e| format!("bad hex in {}: {}", path, e))?)
+}
+
+impl NoiseKeys {
+    /// Noise is enabled when `NOISE_KEY` points at a private key file (hex).
+    /// `NOISE_REMOTE_PUB` optionally pins the peer's public key.
+    pub fn from_env() -> Result<Option<Self>, Box<dyn Error>> {
+        let private = match env::var("NOISE_KEY") {
+            Ok(path) => load_hex(&path)?,
+            Err(_) => return Ok(None),
+        };
+        let remote_public = match env::var("NOISE_REMOTE_PUB") {
+            Ok(path) => Some(load_hex(&path)?),
+            Err(_) => None,
+        };
+        Ok(Some(NoiseKeys {
+            private,
+            remote_public,
+        }))
+    }
+}
+
+/// (private, public) static keypair for `NOISE_PARAMS`.
+pub fn generate_keypair() -> Result<(Vec<u8>, Vec<u8>), snow::Error> {
+    let keypair = Builder::new(NOISE_PARAMS.parse().unwrap()).generate_keypair()?;
+    Ok((keypair.private, keypair.public))
+}
+
+/// Time spent on the channel's cryptography, for overhead measurements.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChannelStats {
+    pub handshake: Duration,
+    pub crypto: Duration,
+}
+
+pub struct SecureChannel {
+    stream: TcpStream,
+    noise: Option<TransportState>,
+    config: FrameConfig,
+    pub stats: ChannelStats,
+}
+
+fn noise_err(e: snow::Error) -> FrameError {
+    FrameError::Noise(e.to_string())
+}
+
+impl SecureChannel {
+    /// Client side. Plain framing if `keys` is `None`.
+    pub async fn connect(
+        stream: TcpStream,
+        keys: Option<&NoiseKeys>,
+        config: FrameConfig,
+    ) -> Result<Self, FrameError> {
+        Self::establish(stream, keys, config, true).await
+    }
+
+    /// Server side. Plain framing if `keys` is `None`.
+    pub async fn accept(
+        stream: TcpStream,
+        keys: Option<&NoiseKeys>,
+        config: FrameConfig,
+    ) -> Result<Self, FrameError> {
+        Self::establish(stream, keys, config, false).await
+    }
+
+    async fn establish(
+        mut stream: TcpStream,
+        keys: Option<&NoiseKeys>,
+        config: FrameConfig,
+        initiator: bool,
+    ) -> Result<Self, FrameError> {
+        let mut stats = ChannelStats::default();
+        let noise = match keys {
+            None => None,
+            Some(keys) => {
+                let start = Instant::now();
+                let builder = Builder::new(NOISE_PARAMS.parse().unwrap()).local_private_key(&keys.private);
+                let handshake = if initiator {
+                    builder.build_initiator()
+                } else {
+                    builder.build_responder()
+                }
+                .map_err(noise_err)?;
+                let transport = run_handshake(&mut stream, handshake, keys, &config, initiator).await?;
+                stats.handshake = start.elapsed();
+                Some(transport)
+            }
+        };
+
+        Ok(SecureChannel {
+            stream,
+            noise,
+            config,
+            stats,
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.noise.is_some()
+    }
+
+    pub async fn write_frame(&mut self, data: &[u8]) -> Result<(), FrameError> {
+        let transport = match self.noise.as_mut() {
+            None => return framing::write_frame(&mut self.stream, data).await,
+            Some(transport) => transport,
+        };
+
+        let mut message = vec![0u8; MAX_NOISE_MSG];
+        let len = u32::try_from(data.len()).map_err(|_| FrameError::TooLarge {
+            len: data.len(),
+            max: u32::MAX as usize,
+        })?;
+
+        let start = Instant::now();
+        let n = transport.write_message(&len.to_be_bytes(), &mut message).map_err(noise_err)?;
+        let mut encrypted = vec![message[..n].to_vec()];
+        for chunk in data.chunks(MAX_CHUNK) {
+            let n = transport.write_message(chunk, &mut message).map_err(noise_err)?;
+            encrypted.push(message[..n].to_vec());
+        }
+        self.stats.crypto += start.elapsed();
+
+        for ciphertext in encrypted {
+            framing::write_frame(&mut self.stream, &ciphertext).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn read_frame(&mut self) -> Result<Vec<u8>, FrameError> {
+        let transport = match self.noise.as_mut() {
+            None => return framing::read_frame(&mut self.stream, &self.config).await,
+            Some(transport) => transport,
+        };
+
+        // Noise 메시지 하나는 최대 65535 바이트, 헤더 이후 청크는 read_timeout 적용
+        let header_config = FrameConfig {
+            max_frame_len: MAX_NOISE_MSG,
+            ..self.config.clone()
+        };
+        let chunk_config = FrameConfig {
+            idle_timeout: self.config.read_timeout,
+            ..header_config.clone()
+        };
+
+        let mut plain = vec![0u8; MAX_NOISE_MSG];
+        let ciphertext = framing::read_frame(&mut self.stream, &header_config).await?;
+        let start = Instant::now();
+        let n = transport.read_message(&ciphertext, &mut plain).map_err(noise_err)?;
+        self.stats.crypto += start.elapsed();
+        if n != 4 {
+            return Err(FrameError::Noise(format!("bad frame header of {} bytes", n)));
+        }
+        let len = u32::from_be_bytes([plain[0], plain[1], plain[2], plain[3]]) as usize;
+        if len > self.config.max_frame_len {
+            return Err(FrameError::TooLarge {
+                len,
+                max: self.config.max_frame_len,
+            });
+        }
+
+        let mut data = Vec::with_capacity(len);
+        while data.len() < len {
+            let ciphertext = framing::read_frame(&mut self.stream, &chunk_config).await?;
+            let start = Instant::now();
+            let n = transport.read_message(&ciphertext, &mut plain).map_err(noise_err)?;
+            self.stats.crypto += start.elapsed();
+            if n == 0 || data.len() + n > len {
+                return Err(FrameError::Noise("chunk does not match frame length".to_string()));
+            }
+            data.extend_from_slice(&plain[..n]);
+        }
+        Ok(data)
+    }
+
+    pub async fn send<T: Serialize>(&mut self, value: &T) -> Result<(), FrameError> {
+        let data = framing::encode(value)?;
+        self.write_frame(&data).await
+    }
+
+    pub async fn recv<T: DeserializeOwned>(&mut self) -> Result<T, FrameError> {
+        let data = self.read_frame().await?;
+        framing::decode(&data, &self.config)
+    }
+}
+
+// XX: -> e / <- e, ee, s, es / -> s, se
+async fn run_handshake(
+    stream: &mut TcpStream,
+    mut handshake: HandshakeState,
+    keys: &NoiseKeys,
+    config: &FrameConfig,
+    initiator: bool,
+) -> Result<TransportState, FrameError> {
+    let config = FrameConfig {
+        max_frame_len: MAX_NOISE_MSG,
+        ..config.clone()
+    };
+    let mut buf = vec![0u8; MAX_NOISE_MSG];
+    let mut our_turn = initiator;
+
+    while !handshake.is_handshake_finished() {
+        if our_turn {
+            let n = handshake.write_message(&[], &mut buf).map_err(noise_err)?;
+            framing::write_frame(stream, &buf[..n]).await?;
+        } else {
+            let message = framing::read_frame(stream, &config).await?;
+            handshake.read_message(&message, &mut buf).map_err(noise_err)?;
+        }
+        our_turn = !our_turn;
+    }
+
+    if let Some(expected) = &keys.remote_public {
+        if handshake.get_remote_static() != Some(expected.as_slice()) {
+            return Err(FrameError::Noise("peer static key is not the pinned one".to_string()));
+        }
+    }
+    handshake.into_transport_mode().map_err(noise_err)
+}
@@ -1,27 +1,38 @@
 use tokio::net::TcpListener;
-use tokio::io::AsyncReadExt;
-use serde::{Deserialize, Serialize};
-use bincode;
 use tokio::sync::Notify;
 use std::env;
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use env_logger;
 use chrono::Local;
 use log::*;
 use std::fs::File;
 use std::io::Write;
+use std::net::SocketAddr;
+use kimchi::mina_curves::pasta::Fp;
 
-#[derive(Serialize, Deserialize)]
-struct NumberRequest {
-    message: String,
-}
+use recursion_exp::{auth, framing, mlp, nonce, protocol, secure, store};
+
+use auth::Allowlist;
+use framing::FrameConfig;
+use mlp::{MlpProof, MlpVerifierIndex};
+use nonce::NonceRegistry;
+use protocol::{NumberRequest, NumberResponse, SubmissionAck};
+use secure::{NoiseKeys, SecureChannel};
+use store::{ClientMetadata, ProofRecord, ProofStore, Verdict};
 
-#[derive(Serialize, Deserialize)]
-struct NumberResponse {
-    public_output: u128,
-    msm_call_count: u64,
-    msm_accumulated_time: f32,
+// 핸드셰이크 이후의 거절은 이유를 담은 ack로 알려서 클라이언트가 Truncated 대신 사유를 받게 함
+async fn reject(channel: &mut SecureChannel, addr: &SocketAddr, message: String) {
+    warn!("Rejected submission from {}: {}", addr, message);
+    let ack = SubmissionAck {
+        accepted: false,
+        message,
+    };
+    if let Err(e) = channel.send(&ack).await {
+        warn!("Failed to send ack to {}; err = {}", addr, e);
+    }
 }
 
 #[tokio::main]
@@ -40,6 +51,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
         "1".to_string().parse::<u32>().unwrap_or(1)
     };
 
+    let allowlist_path = if args.len() > 3 {
+        args[3].clone()
+    } else {
+        "keys/allowlist.txt".to_string()
+    };
+
     // path: log/exp_{client_count}.log
     let path = format!("log/exp{}_{}.log", exp_num, client_count);
     let target = Box::new(File::create(path).expect("Can't create file"));
@@ -59,16 +76,47 @@ async fn main() -> Result<(), Box<dyn Error>> {
             )
         })
         .init();
+
+    // 등록된 클라이언트 공개키 목록
+    let allowlist = Arc::new(Allowlist::load(&allowlist_path)?);
+    info!("Loaded {} registered clients from {}", allowlist.len(), allowlist_path);
+
+    // 수신한 증명을 보관하는 저장소 (기본: ./store)
+    let store_path = env::var("PROOF_STORE").unwrap_or_else(|_| "store".to_string());
+    let store = Arc::new(Mutex::new(ProofStore::open(&store_path)?));
+    info!("Proof store at {}", store_path);
+
+    // 서킷이 결정적이므로 verifier index는 시작할 때 한 번만 만듦
+    let verifier_index: Arc<MlpVerifierIndex> = Arc::new(
+        tokio::task::spawn_blocking(move || mlp::mlp_verifier_index(exp_num as usize)).await?,
+    );
+    info!("Verifier index ready for exp {}", exp_num);
     
     let listener = TcpListener::bind("0.0.0.0:4000").await?;
     info!("Server listening on port 4000");
 
     let connection_start_time = Arc::new(Mutex::new(None::<chrono::NaiveDateTime>));
+    // nonce 유효 시간 (기본 1시간, 큰 exp는 증명 시간이 길어서 여유 있게)
+    let nonce_ttl = env::var("NONCE_TTL_SECS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(3600);
+    let nonces = Arc::new(Mutex::new(NonceRegistry::new(Duration::from_secs(nonce_ttl))));
+
+    // 신뢰할 수 없는 입력에 대한 frame 크기 제한 / 타임아웃
+    let frame_config = Arc::new(FrameConfig::from_env());
+    info!("Frame limits: {:?}", frame_config);
+
+    // NOISE_KEY가 설정되어 있으면 Noise XX로 암호화된 채널 사용
+    let noise_keys = Arc::new(NoiseKeys::from_env()?);
+    info!("Transport: {}", if noise_keys.is_some() { "noise" } else { "plain" });
+
     let public_output_count = Arc::new(Mutex::new(0));
+    let submitted = Arc::new(Mutex::new(BTreeSet::<String>::new()));
     let notify = Arc::new(Notify::new());
     
     loop {
-        let (mut socket, addr) = listener.accept().await?;
+        let (socket, addr) = listener.accept().await?;
         info!("New connection from {}", addr);
         
         // Record the time of the first connection
@@ -79,52 +127,175 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
         let public_output_count = Arc::clone(&public_output_count);
         let notify = Arc::clone(&notify);
+        let store = Arc::clone(&store);
+        let verifier_index = Arc::clone(&verifier_index);
+        let allowlist = Arc::clone(&allowlist);
+        let submitted = Arc::clone(&submitted);
+        let nonces = Arc::clone(&nonces);
+        let frame_config = Arc::clone(&frame_config);
+        let noise_keys = Arc::clone(&noise_keys);
 
         tokio::spawn(async move {
-            // 데이터 길이 읽기 (4바이트)
-            let mut length_buffer = [0u8; 4];
-            if let Err(e) = socket.read_exact(&mut length_buffer).await {
-                eprintln!("Failed to read data length from socket; err = {:?}", e);
-                return;
+            let mut channel =
+                match SecureChannel::accept(socket, (*noise_keys).as_ref(), (*frame_config).clone()).await {
+                    Ok(channel) => channel,
+                    Err(e) => {
+                        warn!("Handshake with {} failed; err = {}", addr, e);
+                        return;
+                    }
+                };
+            if channel.is_encrypted() {
+                info!("Noise handshake with {} took {:?}", addr, channel.stats.handshake);
             }
-            let data_length = u32::from_be_bytes(length_buffer) as usize;
-            info!("data_length: {:?}", data_length);
-        
-            // 데이터 읽기
-            let mut buffer = vec![0u8; data_length];
-            if let Err(e) = socket.read_exact(&mut buffer).await {
-                eprintln!("Failed to read data from socket; err = {:?}", e);
+
+            // 접속마다 새 nonce 발급 후 전송
+            let issued_nonce = nonces.lock().unwrap().issue();
+            let number_request = NumberRequest {
+                message: "Please send your proof".to_string(),
+                nonce: issued_nonce,
+            };
+            if let Err(e) = channel.send(&number_request).await {
+                warn!("Failed to send nonce to {}; err = {}", addr, e);
                 return;
             }
+            info!("Issued nonce {} to {}", issued_nonce, addr);
+
+            // 증명 수신: 크기 제한과 타임아웃이 적용된 frame 단위로 읽음
+            let proof_bytes = match channel.read_frame().await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    reject(&mut channel, &addr, format!("failed to read proof: {}", e)).await;
+                    return;
+                }
+            };
+            info!("data_length: {:?}", proof_bytes.len());
         
             // 메시지 역직렬화
-            let proof: kimchi::poly_commitment::evaluation_proof::OpeningProof<
-                ark_ec::short_weierstrass_jacobian::GroupAffine<kimchi::mina_curves::pasta::VestaParameters>
-            > = bincode::deserialize(&buffer[..]).unwrap();
-            info!("Received message: {:?}", proof);
+            let proof: MlpProof = match framing::decode(&proof_bytes, &frame_config) {
+                Ok(proof) => proof,
+                Err(e) => {
+                    reject(&mut channel, &addr, format!("malformed proof: {}", e)).await;
+                    return;
+                }
+            };
+            info!("Received message: {:?}", proof.proof);
             
-            // public_output 데이터 길이 읽기
-            // 데이터 길이 읽기 (4바이트)
-            let mut length_buffer = [0u8; 4];
-            if let Err(e) = socket.read_exact(&mut length_buffer).await {
-                eprintln!("Failed to read data length from socket; err = {:?}", e);
+            // public_output 등 메타데이터 수신
+            let number_response: NumberResponse = match channel.recv().await {
+                Ok(response) => response,
+                Err(e) => {
+                    reject(&mut channel, &addr, format!("failed to read public_output: {}", e)).await;
+                    return;
+                }
+            };
+            info!("Received public_output: {:?}", number_response.public_output);
+            info!("Received msm_accumulated_time: {:?}", number_response.msm_accumulated_time);
+            info!("Received msm_call_count: {:?}", number_response.msm_call_count);
+
+            // 서명 확인: allowlist에 없는 키나 잘못된 서명은 카운트하지 않음
+            let client_name = match allowlist.lookup(&number_response.public_key) {
+                Some((name, pub_key)) => {
+                    let submission = auth::Submission::new(
+                        &proof_bytes,
+                        number_response.exp_num,
+                        number_response.public_output,
+                        number_response.nonce,
+                    );
+                    if !auth::verify_submission(pub_key, &submission, &number_response.signature) {
+                        reject(&mut channel, &addr, format!("bad signature for {}", name)).await;
+                        return;
+                    }
+                    name.to_string()
+                }
+                None => {
+                    let message = format!("unregistered key {}", number_response.public_key);
+                    reject(&mut channel, &addr, message).await;
+                    return;
+                }
+            };
+            info!("Authenticated client: {}", client_name);
+
+            // nonce 확인: 이 접속에 발급한 nonce여야 하고, 증명의 마지막 공개 입력과 같아야 함
+            if number_response.nonce != issued_nonce
+                || number_response.public_input.last() != Some(&(number_response.nonce as u128))
+            {
+                let message = format!("nonce does not match the issued one ({})", client_name);
+                reject(&mut channel, &addr, message).await;
                 return;
             }
-            let data_length = u32::from_be_bytes(length_buffer) as usize;
-            info!("data_length: {:?}", data_length);
-
-            // 데이터 읽기
-            let mut buffer = vec![0u8; data_length];
-            if let Err(e) = socket.read_exact(&mut buffer).await {
-                eprintln!("Failed to read data from socket; err = {:?}", e);
+            let consumed = nonces.lock().unwrap().consume(issued_nonce);
+            if let Err(e) = consumed {
+                reject(&mut channel, &addr, format!("{} ({})", e, client_name)).await;
                 return;
             }
 
-            // 메시지 역직렬화
-            let number_response: NumberResponse = bincode::deserialize(&buffer[..]).unwrap();
-            info!("Received public_output: {:?}", number_response.public_output);
-            info!("Received msm_accumulated_time: {:?}", number_response.msm_accumulated_time);
-            info!("Received msm_call_count: {:?}", number_response.msm_call_count);
+            // 증명 검증 (CPU 작업이므로 blocking 스레드에서 실행).
+            // 공개 입력은 클라이언트가 보낸 값이 아니라 서버가 정한 입력 + 발급한 nonce 로 검증
+            let public_input = number_response.public_input.clone();
+            let submitted_exp = number_response.exp_num;
+            let verify_start = Instant::now();
+            let verdict = tokio::task::spawn_blocking(move || {
+                if submitted_exp != exp_num {
+                    return Verdict::Invalid(format!(
+                        "exp mismatch: submitted {}, server runs {}",
+                        submitted_exp, exp_num
+                    ));
+                }
+                let expected = mlp::mlp_public_input(issued_nonce);
+                let public: Vec<Fp> = public_input.iter().map(|x| Fp::from(*x)).collect();
+                if public.len() != expected.len() || public[..mlp::INPUT_SIZE] != expected[..mlp::INPUT_SIZE] {
+                    return Verdict::Invalid(format!(
+                        "public input {:?} is not the server's input for nonce {}",
+                        public_input, issued_nonce
+                    ));
+                }
+                match mlp::verify_mlp_proof(&verifier_index, &proof, &expected) {
+                    Ok(()) => Verdict::Valid,
+                    Err(e) => Verdict::Invalid(format!("{:?}", e)),
+                }
+            })
+            .await
+            .unwrap_or_else(|e| Verdict::Invalid(format!("verifier panicked: {}", e)));
+            info!("Verdict: {:?} ({:?})", verdict, verify_start.elapsed());
+
+            let record = ProofRecord {
+                hash: store::content_hash(&proof_bytes),
+                received_at: Local::now().to_rfc3339(),
+                circuit_id: store::circuit_id(number_response.exp_num),
+                exp_num: number_response.exp_num,
+                public_input: number_response.public_input,
+                public_output: number_response.public_output,
+                proof_len: proof_bytes.len(),
+                client: ClientMetadata {
+                    addr: addr.to_string(),
+                    client_id: Some(client_name.clone()),
+                    msm_call_count: number_response.msm_call_count,
+                    msm_accumulated_time: number_response.msm_accumulated_time,
+                },
+                verdict,
+            };
+            if let Err(e) = store.lock().unwrap().append(&proof_bytes, &record) {
+                error!("Failed to store proof {}; err = {:?}", record.hash, e);
+            } else {
+                info!("Stored proof {}", record.hash);
+            }
+
+            // 클라이언트가 제출 지연 시간을 잴 수 있도록 결과 응답
+            let ack = SubmissionAck {
+                accepted: record.verdict == Verdict::Valid,
+                message: format!("{:?}", record.verdict),
+            };
+            if let Err(e) = channel.send(&ack).await {
+                warn!("Failed to send ack to {}; err = {}", addr, e);
+            }
+            info!("Channel crypto time for {}: {:?}", addr, channel.stats.crypto);
+
+            // 무효한 증명은 감사용으로 저장만 하고, 제출/완료 카운트에는 넣지 않음
+            if record.verdict != Verdict::Valid {
+                info!("Not counting invalid submission from {}", client_name);
+                return;
+            }
+            submitted.lock().unwrap().insert(client_name);
 
             // Increase the count and check if it is the 32nd client
             let mut count = public_output_count.lock().unwrap();
@@ -135,6 +306,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 let start_time = connection_start_time.lock().unwrap().clone().unwrap();
                 let duration = end_time - start_time;
                 info!("Time taken for {} public_output: {:?}", client_count, duration);
+
+                // 등록된 클라이언트 중 제출한/하지 않은 클라이언트
+                let submitted = submitted.lock().unwrap();
+                let (done, missing): (Vec<&str>, Vec<&str>) =
+                    allowlist.names().partition(|name| submitted.contains(*name));
+                info!("Registered clients submitted: {:?}", done);
+                info!("Registered clients missing: {:?}", missing);
                 std::process::exit(0);
             }
 
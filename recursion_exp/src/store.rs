@@ -0,0 +1,144 @@
+//! Append-only on-disk store for received proofs.
+//!
+//! Layout under the store root:
+//!
+//! ```text
+//! objects/<sha256>.bin   raw proof bytes, content addressed
+//! index.jsonl            one `ProofRecord` per submission, never rewritten
+//! ```
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "index.jsonl";
+const OBJECTS_DIR: &str = "objects";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum Verdict {
+    Valid,
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientMetadata {
+    pub addr: String,
+    #[serde(default)]
+    pub client_id: Option<String>,
+    pub msm_call_count: u64,
+    pub msm_accumulated_time: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofRecord {
+    pub hash: String,
+    pub received_at: String,
+    pub circuit_id: String,
+    pub exp_num: u32,
+    pub public_input: Vec<u128>,
+    pub public_output: u128,
+    pub proof_len: usize,
+    pub client: ClientMetadata,
+    pub verdict: Verdict,
+}
+
+/// SHA-256 of the proof bytes, hex encoded. Used as the object key.
+pub fn content_hash(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub fn circuit_id(exp_num: u32) -> String {
+    format!("kimchi_mlp_exp{}", exp_num)
+}
+
+pub struct ProofStore {
+    root: PathBuf,
+}
+
+impl ProofStore {
+    pub fn open(root: impl AsRef<Path>) -> io::Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(root.join(OBJECTS_DIR))?;
+        Ok(ProofStore { root })
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join(OBJECTS_DIR).join(format!("{}.bin", hash))
+    }
+
+    /// Writes the proof object (once per hash) and appends `record` to the index.
+    pub fn append(&self, proof_bytes: &[u8], record: &ProofRecord) -> io::Result<()> {
+        if record.hash != content_hash(proof_bytes) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record hash does not match proof bytes",
+            ));
+        }
+
+        let object_path = self.object_path(&record.hash);
+        if !object_path.exists() {
+            // 임시 파일에 쓴 뒤 rename 해서 반쯤 쓰인 object가 남지 않게 함
+            let tmp_path = object_path.with_extension("tmp");
+            let mut f = File::create(&tmp_path)?;
+            f.write_all(proof_bytes)?;
+            f.sync_all()?;
+            fs::rename(&tmp_path, &object_path)?;
+        }
+
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        let mut index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root.join(INDEX_FILE))?;
+        index.write_all(line.as_bytes())?;
+        index.sync_data()
+    }
+
+    /// All records in submission order.
+    pub fn list(&self) -> io::Result<Vec<ProofRecord>> {
+        let path = self.root.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut records = vec![];
+        for (i, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", INDEX_FILE, i + 1, e),
+                )
+            })?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Records whose hash starts with `prefix`, oldest first.
+    pub fn find(&self, prefix: &str) -> io::Result<Vec<ProofRecord>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|r| r.hash.starts_with(prefix))
+            .collect())
+    }
+
+    /// Loads the proof bytes for `hash` and checks them against the hash.
+    pub fn load_proof(&self, hash: &str) -> io::Result<Vec<u8>> {
+        let bytes = fs::read(self.object_path(hash))?;
+        if content_hash(&bytes) != hash {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("object {} is corrupted", hash),
+            ));
+        }
+        Ok(bytes)
+    }
+}
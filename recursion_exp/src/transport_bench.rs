@@ -0,0 +1,160 @@
+//! Plain vs Noise round-trip latency for one and the same submission.
+//!
+//! Runs both ends over loopback in one process: the client side sends the encoded
+//! proof and its signed `NumberResponse`, the server side reads both frames and
+//! answers with a `SubmissionAck`. No verification happens, so the difference
+//! between the two transports is the framing/Noise overhead only.
+use mina_signer::Keypair;
+use std::env;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream};
+
+use recursion_exp::{auth, framing, mlp, protocol, secure};
+
+use framing::FrameConfig;
+use mlp::fp_to_integer;
+use protocol::{NumberResponse, SubmissionAck};
+use secure::{NoiseKeys, SecureChannel};
+
+fn usage() -> ! {
+    eprintln!("usage: transport_bench [exp] [runs]");
+    std::process::exit(2);
+}
+
+fn parse_arg(args: &[String], i: usize, default: usize) -> usize {
+    match args.get(i) {
+        None => default,
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            eprintln!("invalid number: {}", value);
+            usage()
+        }),
+    }
+}
+
+struct Sample {
+    handshake: Duration,
+    round_trip: Duration,
+    crypto: Duration,
+}
+
+// 서버 쪽: 증명/메타데이터 frame 두 개를 받고 ack 응답
+async fn serve(listener: &TcpListener, keys: Option<&NoiseKeys>, config: FrameConfig) -> Result<(), Box<dyn Error>> {
+    let (socket, _) = listener.accept().await?;
+    let mut channel = SecureChannel::accept(socket, keys, config).await?;
+    let proof = channel.read_frame().await?;
+    let _: NumberResponse = channel.recv().await?;
+    let ack = SubmissionAck {
+        accepted: true,
+        message: format!("{} bytes", proof.len()),
+    };
+    channel.send(&ack).await?;
+    Ok(())
+}
+
+async fn submit(
+    addr: &str,
+    keys: Option<&NoiseKeys>,
+    config: FrameConfig,
+    proof_bytes: &[u8],
+    response: &NumberResponse,
+) -> Result<Sample, Box<dyn Error>> {
+    let socket = TcpStream::connect(addr).await?;
+    let mut channel = SecureChannel::connect(socket, keys, config).await?;
+
+    // client.rs 의 submit_time 과 같은 구간: 증명 전송부터 ack 수신까지
+    let start = Instant::now();
+    channel.write_frame(proof_bytes).await?;
+    channel.send(response).await?;
+    let _: SubmissionAck = channel.recv().await?;
+    Ok(Sample {
+        handshake: channel.stats.handshake,
+        round_trip: start.elapsed(),
+        crypto: channel.stats.crypto,
+    })
+}
+
+async fn measure(
+    label: &str,
+    keys: Option<(NoiseKeys, NoiseKeys)>,
+    runs: usize,
+    proof_bytes: &[u8],
+    response: &NumberResponse,
+) -> Result<Duration, Box<dyn Error>> {
+    let config = FrameConfig::from_env();
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?.to_string();
+    let (client_keys, server_keys) = match &keys {
+        Some((client, server)) => (Some(client), Some(server)),
+        None => (None, None),
+    };
+
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let (served, sample) = tokio::join!(
+            serve(&listener, server_keys, config.clone()),
+            submit(&addr, client_keys, config.clone(), proof_bytes, response)
+        );
+        served?;
+        samples.push(sample?);
+    }
+
+    let mean = |f: fn(&Sample) -> Duration| samples.iter().map(f).sum::<Duration>() / runs as u32;
+    let mut round_trips: Vec<Duration> = samples.iter().map(|s| s.round_trip).collect();
+    round_trips.sort();
+    let median = round_trips[runs / 2];
+    println!("{}_handshake_time: {:?}", label, mean(|s| s.handshake).as_secs_f32());
+    println!("{}_round_trip_mean: {:?}", label, mean(|s| s.round_trip).as_secs_f32());
+    println!("{}_round_trip_median: {:?}", label, median.as_secs_f32());
+    println!("{}_crypto_time: {:?}", label, mean(|s| s.crypto).as_secs_f32());
+    Ok(median)
+}
+
+fn noise_pair() -> Result<(NoiseKeys, NoiseKeys), Box<dyn Error>> {
+    let (client_private, client_public) = secure::generate_keypair()?;
+    let (server_private, server_public) = secure::generate_keypair()?;
+    let client = NoiseKeys {
+        private: client_private,
+        remote_public: Some(server_public),
+    };
+    let server = NoiseKeys {
+        private: server_private,
+        remote_public: Some(client_public),
+    };
+    Ok((client, server))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let exp = parse_arg(&args, 1, 1);
+    let runs = parse_arg(&args, 2, 10);
+    if runs == 0 {
+        usage();
+    }
+
+    // 두 전송 방식 모두 같은 증명과 서명된 메타데이터를 보냄
+    let nonce = 0;
+    let (proof, public_input, public_output) = mlp::mlp_by_depth(exp, nonce);
+    let proof_bytes = framing::encode(&proof)?;
+    let public_output = fp_to_integer(public_output[0]);
+    let keypair = Keypair::rand(&mut rand::rngs::OsRng).map_err(|e| format!("{:?}", e))?;
+    let submission = auth::Submission::new(&proof_bytes, exp as u32, public_output, nonce);
+    let response = NumberResponse {
+        public_output,
+        msm_call_count: 0,
+        msm_accumulated_time: 0.0,
+        exp_num: exp as u32,
+        public_input: public_input.iter().map(|x| fp_to_integer(*x)).collect(),
+        nonce,
+        public_key: keypair.public.into_address(),
+        signature: auth::sign_submission(&keypair, &submission),
+    };
+    println!("proof_bytes: {}", proof_bytes.len());
+    println!("runs: {}", runs);
+
+    let plain = measure("plain", None, runs, &proof_bytes, &response).await?;
+    let noise = measure("noise", Some(noise_pair()?), runs, &proof_bytes, &response).await?;
+    println!("noise_overhead: {:?}", (noise.as_secs_f64() - plain.as_secs_f64()) as f32);
+    Ok(())
+}
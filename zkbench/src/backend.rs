@@ -0,0 +1,224 @@
+//! Common interface over proving backends, so one driver can benchmark kimchi
+//! and RISC Zero on the same model shape and input.
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::measure;
+use crate::stats::Summary;
+use model_spec::ModelSpec;
+
+pub type BackendError = Box<dyn Error + Send + Sync>;
+
+/// Model shape and input shared by all backends, loaded from JSON:
+///
+/// ```json
+/// { "name": "mlp_2_1_1", "layers": [2, 1, 1], "input": [[1.0, 1.0], [2.0, 3.0]],
+///   "spec": "../mlp_risczero/model_data/mlp_spec.json" }
+/// ```
+///
+/// `spec` is the `model_spec` file (relative to the workload file) with the weights;
+/// every backend runs exactly that network, and its layer sizes must match `layers`.
+/// Instead of inline `input`,
+/// `"input_file"` may name a JSON or `model_spec::binary` file of rows, also relative
+/// to the workload file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Workload {
+    pub name: String,
+    /// Layer widths, input width first.
+    pub layers: Vec<usize>,
+    /// One row per sample, each `layers[0]` wide.
+    #[serde(default)]
+    pub input: Vec<Vec<f64>>,
+    #[serde(default)]
+    pub input_file: Option<PathBuf>,
+    pub spec: PathBuf,
+}
+
+impl Workload {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let mut workload: Workload = serde_json::from_str(&text)?;
+        if let Some(dir) = path.parent() {
+            workload.spec = dir.join(&workload.spec);
+            if let Some(file) = workload.input_file.as_mut() {
+                *file = dir.join(&*file);
+            }
+        }
+        if let Some(file) = &workload.input_file {
+            if !workload.input.is_empty() {
+                return Err("workload has both input and input_file".into());
+            }
+            workload.input = model_spec::binary::load_input(file)?;
+        }
+        if workload.layers.len() < 2 {
+            return Err("workload needs an input width and at least one layer".into());
+        }
+        if workload.input.is_empty() || workload.input.iter().any(|row| row.len() != workload.layers[0]) {
+            return Err(format!("every input row must have {} values", workload.layers[0]).into());
+        }
+        let spec = workload.model()?;
+        if spec.layer_sizes() != workload.layers {
+            return Err(format!(
+                "spec {} has layers {:?}, workload {} wants {:?}",
+                spec.name,
+                spec.layer_sizes(),
+                workload.name,
+                workload.layers
+            )
+            .into());
+        }
+        Ok(workload)
+    }
+
+    /// Loads the model `spec` points at.
+    pub fn model(&self) -> Result<ModelSpec, BackendError> {
+        ModelSpec::load(&self.spec).map_err(|e| format!("{}: {}", self.spec.display(), e).into())
+    }
+
+    pub fn input_width(&self) -> usize {
+        self.layers[0]
+    }
+
+    pub fn hidden_layers(&self) -> &[usize] {
+        &self.layers[1..]
+    }
+
+    pub fn batch(&self) -> usize {
+        self.input.len()
+    }
+}
+
+pub trait ZkBackend {
+    type Proof;
+
+    fn name(&self) -> &'static str;
+
+    /// Builds whatever proving needs for `workload` (circuit and index, guest env, ...).
+    /// Called once before any `prove`.
+    fn setup(&mut self, workload: &Workload) -> Result<(), BackendError>;
+
+    fn prove(&mut self, workload: &Workload) -> Result<Self::Proof, BackendError>;
+
+    fn verify(&self, proof: &Self::Proof) -> Result<(), BackendError>;
+
+    /// Serialized proof size in bytes.
+    fn proof_size(&self, proof: &Self::Proof) -> usize;
+
+    /// Backend-specific numbers from the last `prove` (MSM time, cycle counts, ...).
+    fn metrics(&self) -> Vec<(&'static str, f64)>;
+
+    /// Model outputs the proof commits to, one row per input sample, or `None` if the backend
+    /// does not expose them. Runs outside the timed verify, so checks of the proof's public
+    /// data against what was sent belong here and fail the run with an error.
+    fn outputs(&self, _proof: &Self::Proof) -> Result<Option<Vec<Vec<f64>>>, BackendError> {
+        Ok(None)
+    }
+}
+
+pub struct BackendReport {
+    pub backend: &'static str,
+    pub workload: String,
+    pub setup_time: f64,
+    pub prove: Summary,
+    pub verify: Summary,
+    pub proof_size: usize,
+    pub metrics: Vec<(&'static str, f64)>,
+    /// Outputs of the last proof, if the backend exposes them.
+    pub outputs: Option<Vec<Vec<f64>>>,
+    /// Process CPU time over wall time of the timed runs, in percent.
+    pub cpu_percent: f64,
+    /// Peak RSS of the process after the runs, in KiB.
+    pub memory_kb: u64,
+}
+
+impl BackendReport {
+    pub fn print(&self) {
+        println!("backend: {} workload: {}", self.backend, self.workload);
+        println!("  setup_time: {:.3}", self.setup_time);
+        println!(
+            "  prove_time: {:.3} (stddev {:.3}, n={})",
+            self.prove.mean, self.prove.stddev, self.prove.n
+        );
+        println!(
+            "  verify_time: {:.3} (stddev {:.3}, n={})",
+            self.verify.mean, self.verify.stddev, self.verify.n
+        );
+        println!("  proof_size: {}", self.proof_size);
+        for (name, value) in &self.metrics {
+            println!("  {}: {}", name, value);
+        }
+        if let Some(outputs) = &self.outputs {
+            println!("  outputs: {:?}", outputs);
+        }
+    }
+
+    /// Writes `{"backend", "workload", "outputs"}` JSON, the format `equivcheck` reads.
+    pub fn write_outputs(&self, path: impl AsRef<Path>) -> Result<(), BackendError> {
+        let outputs = self
+            .outputs
+            .as_ref()
+            .ok_or_else(|| format!("{} produced no outputs", self.backend))?;
+        let json = serde_json::json!({
+            "backend": self.backend,
+            "workload": self.workload,
+            "outputs": outputs,
+        });
+        fs::write(path, serde_json::to_string_pretty(&json)?)?;
+        Ok(())
+    }
+}
+
+/// Sets up `backend` once, then proves and verifies `warmup + runs` times.
+/// Only the last `runs` iterations are timed; metrics come from the final prove.
+pub fn run<B: ZkBackend>(
+    backend: &mut B,
+    workload: &Workload,
+    warmup: usize,
+    runs: usize,
+) -> Result<BackendReport, BackendError> {
+    let start = Instant::now();
+    backend.setup(workload)?;
+    let setup_time = start.elapsed().as_secs_f64();
+
+    let mut prove_times = vec![];
+    let mut verify_times = vec![];
+    let mut proof_size = 0;
+    let mut outputs = None;
+    let (mut timed_wall, mut timed_cpu) = (Duration::ZERO, Duration::ZERO);
+    for i in 0..warmup + runs.max(1) {
+        let (run_start, cpu_start) = (Instant::now(), measure::cpu_time());
+        let start = Instant::now();
+        let proof = backend.prove(workload)?;
+        let prove_time = start.elapsed().as_secs_f64();
+
+        let start = Instant::now();
+        backend.verify(&proof)?;
+        let verify_time = start.elapsed().as_secs_f64();
+
+        if i >= warmup {
+            prove_times.push(prove_time);
+            verify_times.push(verify_time);
+            timed_wall += run_start.elapsed();
+            timed_cpu += measure::cpu_time() - cpu_start;
+        }
+        proof_size = backend.proof_size(&proof);
+        outputs = backend.outputs(&proof)?;
+    }
+
+    Ok(BackendReport {
+        backend: backend.name(),
+        workload: workload.name.clone(),
+        setup_time,
+        prove: Summary::of(&prove_times).unwrap(),
+        verify: Summary::of(&verify_times).unwrap(),
+        proof_size,
+        metrics: backend.metrics(),
+        outputs,
+        cpu_percent: timed_cpu.as_secs_f64() / timed_wall.as_secs_f64().max(f64::EPSILON) * 100.0,
+        memory_kb: measure::peak_rss_kb(),
+    })
+}
@@ -0,0 +1,350 @@
+//! Result rows in the existing `framework,model,exp_num,proving_time,memory_usage,cpu_usage`
+//! schema, with the extra measurements appended as new columns.
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use model_spec::{Activation, InferenceMode};
+
+use crate::backend::BackendReport;
+use crate::measure::Measurement;
+use crate::stats::Summary;
+
+pub const HEADER: &str =
+    "framework,model,exp_num,proving_time,memory_usage,cpu_usage,wall_time,user_time,sys_time,exit_code,run,threads";
+
+pub const SUMMARY_HEADER: &str =
+    "framework,model,exp_num,threads,metric,n,mean,median,stddev,ci95_low,ci95_high,cv,noisy";
+
+pub const BACKEND_HEADER: &str =
+    "backend,workload,setup_time,prove_time,prove_stddev,verify_time,verify_stddev,runs,proof_size,metrics";
+
+/// `HEADER`'s first six columns, then the `BACKEND_HEADER` measurements, so shared-format
+/// consumers can read in-process backends next to the scripted frameworks.
+pub const SHARED_BACKEND_HEADER: &str = "framework,model,exp_num,proving_time,memory_usage,cpu_usage,\
+workload,setup_time,prove_stddev,verify_time,verify_stddev,runs,proof_size,metrics";
+
+pub const SCALING_HEADER: &str = "framework,model,exp_num,threads,wall_time,speedup,efficiency";
+
+pub const LAYER_PROFILE_HEADER: &str =
+    "workload,mode,guest,layer,inputs,outputs,activation,macs,cycles,cycles_per_mac";
+
+// 스레드 수를 고정하지 않은 실행은 빈 칸
+fn threads_cell(threads: Option<u32>) -> String {
+    threads.map(|n| n.to_string()).unwrap_or_default()
+}
+
+fn optional_cell(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.4}", v)).unwrap_or_default()
+}
+
+pub struct ResultRow<'a> {
+    pub framework: &'a str,
+    pub model: &'a str,
+    pub exp_num: u32,
+    /// 1-based repetition index (warmup runs are not written).
+    pub run: u32,
+    /// `RAYON_NUM_THREADS` the run was pinned to, if any.
+    pub threads: Option<u32>,
+    pub measurement: Measurement,
+}
+
+impl ResultRow<'_> {
+    /// `proving_time` is wall-clock seconds now, not gtime's user time.
+    pub fn to_csv(&self) -> String {
+        let m = &self.measurement;
+        format!(
+            "{},{},{},{:.2},{},{:.0}%,{:.3},{:.3},{:.3},{},{},{}",
+            self.framework,
+            self.model,
+            self.exp_num,
+            m.wall.as_secs_f64(),
+            m.max_rss_kb,
+            m.cpu_percent(),
+            m.wall.as_secs_f64(),
+            m.user.as_secs_f64(),
+            m.sys.as_secs_f64(),
+            m.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "signal".to_string()),
+            self.run,
+            threads_cell(self.threads),
+        )
+    }
+}
+
+/// One metric summarised over the repetitions of a (framework, model, exp_num).
+pub struct SummaryRow<'a> {
+    pub framework: &'a str,
+    pub model: &'a str,
+    pub exp_num: u32,
+    pub threads: Option<u32>,
+    pub metric: &'a str,
+    pub summary: Summary,
+    pub noisy: bool,
+}
+
+impl SummaryRow<'_> {
+    pub fn to_csv(&self) -> String {
+        let s = &self.summary;
+        format!(
+            "{},{},{},{},{},{},{:.4},{:.4},{:.4},{},{},{:.4},{}",
+            self.framework,
+            self.model,
+            self.exp_num,
+            threads_cell(self.threads),
+            self.metric,
+            s.n,
+            s.mean,
+            s.median,
+            s.stddev,
+            optional_cell(s.ci95.map(|ci| ci.0)),
+            optional_cell(s.ci95.map(|ci| ci.1)),
+            s.cv(),
+            self.noisy,
+        )
+    }
+}
+
+/// Mean wall time at one thread count, relative to the first thread count measured.
+pub struct ScalingRow<'a> {
+    pub framework: &'a str,
+    pub model: &'a str,
+    pub exp_num: u32,
+    pub threads: u32,
+    pub wall_time: f64,
+    pub speedup: f64,
+    pub efficiency: f64,
+}
+
+impl ScalingRow<'_> {
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{:.4},{:.3},{:.3}",
+            self.framework, self.model, self.exp_num, self.threads, self.wall_time, self.speedup, self.efficiency,
+        )
+    }
+}
+
+/// zkVM cycles of one dense layer per sample, next to its shape.
+pub struct LayerProfileRow<'a> {
+    pub workload: &'a str,
+    pub mode: InferenceMode,
+    pub guest: &'a str,
+    pub layer: usize,
+    pub inputs: usize,
+    pub outputs: usize,
+    pub activation: Activation,
+    /// Cycles per sample, averaged over the samples.
+    pub cycles: f64,
+}
+
+impl LayerProfileRow<'_> {
+    /// `macs` is one multiply-accumulate per weight.
+    pub fn to_csv(&self) -> String {
+        let macs = self.inputs * self.outputs;
+        format!(
+            "{},{},{},{},{},{},{},{},{:.1},{:.3}",
+            self.workload,
+            self.mode,
+            self.guest,
+            self.layer,
+            self.inputs,
+            self.outputs,
+            self.activation,
+            macs,
+            self.cycles,
+            self.cycles / macs.max(1) as f64,
+        )
+    }
+}
+
+/// Splits a workload name like `mlp3` into the shared `model` and `exp_num` cells;
+/// other names (`mlp_2_1_1`) are the model with an empty `exp_num`.
+fn model_and_exp(workload: &str) -> (&str, Option<u32>) {
+    let model = workload.trim_end_matches(|c: char| c.is_ascii_digit());
+    if model.ends_with(|c: char| c.is_ascii_alphabetic()) && model.len() < workload.len() {
+        (model, workload[model.len()..].parse().ok())
+    } else {
+        (workload, None)
+    }
+}
+
+/// A `BackendReport` as a row of the shared `framework,model,exp_num,...` schema:
+/// `proving_time` is the mean prove time, `memory_usage` the peak RSS in KiB.
+pub struct SharedBackendRow<'a>(pub &'a BackendReport);
+
+impl SharedBackendRow<'_> {
+    pub fn to_csv(&self) -> String {
+        let report = self.0;
+        let (model, exp_num) = model_and_exp(&report.workload);
+        let metrics: Vec<String> = report.metrics.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!(
+            "{},{},{},{:.2},{},{:.0}%,{},{:.4},{:.4},{:.4},{:.4},{},{},{}",
+            report.backend,
+            model,
+            exp_num.map(|n| n.to_string()).unwrap_or_default(),
+            report.prove.mean,
+            report.memory_kb,
+            report.cpu_percent,
+            report.workload,
+            report.setup_time,
+            report.prove.stddev,
+            report.verify.mean,
+            report.verify.stddev,
+            report.prove.n,
+            report.proof_size,
+            metrics.join(";"),
+        )
+    }
+}
+
+impl BackendReport {
+    /// Metrics go into one `name=value;...` cell since they differ per backend.
+    pub fn to_csv(&self) -> String {
+        let metrics: Vec<String> = self.metrics.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!(
+            "{},{},{:.4},{:.4},{:.4},{:.4},{:.4},{},{},{}",
+            self.backend,
+            self.workload,
+            self.setup_time,
+            self.prove.mean,
+            self.prove.stddev,
+            self.verify.mean,
+            self.verify.stddev,
+            self.prove.n,
+            self.proof_size,
+            metrics.join(";"),
+        )
+    }
+}
+
+pub struct ResultWriter {
+    file: File,
+}
+
+impl ResultWriter {
+    /// Truncates `path` and writes the header, like the old shell scripts.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open(path, HEADER, false)
+    }
+
+    /// Appends to `path`, writing the header only if the file is new or empty.
+    pub fn append(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open(path, HEADER, true)
+    }
+
+    /// Same as `create`/`append` but for `SummaryRow`s.
+    pub fn summary(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        Self::open(path, SUMMARY_HEADER, append)
+    }
+
+    /// Same as `create`/`append` but for `SharedBackendRow`s.
+    pub fn shared_backend(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        Self::open(path, SHARED_BACKEND_HEADER, append)
+    }
+
+    /// Same as `create`/`append` but for `ScalingRow`s.
+    pub fn scaling(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        Self::open(path, SCALING_HEADER, append)
+    }
+
+    /// Same as `create`/`append` but for `BackendReport`s.
+    pub fn backend(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        Self::open(path, BACKEND_HEADER, append)
+    }
+
+    /// Same as `create`/`append` but for `LayerProfileRow`s.
+    pub fn layer_profile(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        Self::open(path, LAYER_PROFILE_HEADER, append)
+    }
+
+    fn open(path: impl AsRef<Path>, header: &str, append: bool) -> io::Result<Self> {
+        let mut file = if append {
+            OpenOptions::new().create(true).append(true).open(path)?
+        } else {
+            File::create(path)?
+        };
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", header)?;
+        }
+        Ok(ResultWriter { file })
+    }
+
+    pub fn write(&mut self, row: &ResultRow) -> io::Result<()> {
+        self.write_line(&row.to_csv())
+    }
+
+    pub fn write_summary(&mut self, row: &SummaryRow) -> io::Result<()> {
+        self.write_line(&row.to_csv())
+    }
+
+    pub fn write_scaling(&mut self, row: &ScalingRow) -> io::Result<()> {
+        self.write_line(&row.to_csv())
+    }
+
+    pub fn write_backend(&mut self, report: &BackendReport) -> io::Result<()> {
+        self.write_line(&report.to_csv())
+    }
+
+    pub fn write_shared_backend(&mut self, row: &SharedBackendRow) -> io::Result<()> {
+        self.write_line(&row.to_csv())
+    }
+
+    pub fn write_layer_profile(&mut self, row: &LayerProfileRow) -> io::Result<()> {
+        self.write_line(&row.to_csv())
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workload_name_splits_into_model_and_exp() {
+        assert_eq!(model_and_exp("mlp3"), ("mlp", Some(3)));
+        assert_eq!(model_and_exp("mlp13"), ("mlp", Some(13)));
+        assert_eq!(model_and_exp("mlp_2_1_1"), ("mlp_2_1_1", None));
+        assert_eq!(model_and_exp("friedman1_trained"), ("friedman1_trained", None));
+        assert_eq!(model_and_exp("42"), ("42", None));
+    }
+
+    #[test]
+    fn shared_row_starts_with_the_shared_columns() {
+        let report = BackendReport {
+            backend: "risc0_float_composite",
+            workload: "mlp3".to_string(),
+            setup_time: 0.5,
+            prove: Summary::of(&[2.0, 4.0]).unwrap(),
+            verify: Summary::of(&[0.01, 0.01]).unwrap(),
+            proof_size: 1234,
+            metrics: vec![("total_cycles", 65536.0)],
+            outputs: None,
+            cpu_percent: 390.4,
+            memory_kb: 2048,
+        };
+        let row = SharedBackendRow(&report).to_csv();
+        assert_eq!(row.split(',').count(), SHARED_BACKEND_HEADER.split(',').count());
+        assert!(row.starts_with("risc0_float_composite,mlp,3,3.00,2048,390%,mlp3,"));
+        assert!(row.ends_with(",2,1234,total_cycles=65536"));
+    }
+
+    #[test]
+    fn layer_profile_row_has_macs_and_cycles_per_mac() {
+        let row = LayerProfileRow {
+            workload: "mlp3",
+            mode: InferenceMode::Fixed,
+            guest: "tensor",
+            layer: 1,
+            inputs: 4,
+            outputs: 2,
+            activation: Activation::Relu,
+            cycles: 100.0,
+        };
+        assert_eq!(row.to_csv(), "mlp3,fixed,tensor,1,4,2,relu,8,100.0,12.500");
+        assert_eq!(row.to_csv().split(',').count(), LAYER_PROFILE_HEADER.split(',').count());
+    }
+}
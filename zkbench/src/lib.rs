@@ -0,0 +1,5 @@
+pub mod backend;
+pub mod csv;
+pub mod measure;
+pub mod stats;
+pub mod suite;
@@ -0,0 +1,364 @@
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::process::{self, Command};
+
+use zkbench::csv::{ResultRow, ResultWriter, ScalingRow, SummaryRow};
+use zkbench::measure::{run_measured, Measurement};
+use zkbench::stats::{scaling, Summary};
+use zkbench::backend::{BackendError, Workload};
+use zkbench::suite::{self, BackendBinary};
+
+// 반복 실행 요약에 쓰는 지표 (이름, 값)
+type Metric = (&'static str, fn(&Measurement) -> f64);
+
+const METRICS: &[Metric] = &[
+    ("wall_time", |m| m.wall.as_secs_f64()),
+    ("user_time", |m| m.user.as_secs_f64()),
+    ("sys_time", |m| m.sys.as_secs_f64()),
+    ("memory_usage", |m| m.max_rss_kb as f64),
+];
+
+fn usage() -> ! {
+    let names: Vec<&str> = suite::SUITES.iter().map(|s| s.name).collect();
+    let backends: Vec<&str> = suite::BACKENDS.iter().map(|b| b.name).collect();
+    eprintln!("usage: zkbench <suite> [options]");
+    eprintln!("       zkbench backends <workload.json> [--backends a,b] [--warmup N] [--runs N]");
+    eprintln!("                [--out FILE] [--shared-out FILE] [--log-dir DIR]");
+    eprintln!("  suites: {}", names.join(", "));
+    eprintln!("  backends: {} (each runs the workload's spec on its inputs)", backends.join(", "));
+    eprintln!("  --exps 1..15 | 1,3,5   experiment numbers (default: suite range)");
+    eprintln!("  --frameworks a,b       subset of the suite's frameworks");
+    eprintln!("  --out FILE             result CSV (default: experiment_<suite>.csv)");
+    eprintln!("  --append               append to FILE instead of truncating it");
+    eprintln!("  --log-dir DIR          per-run stdout/stderr (default: logs)");
+    eprintln!("  --warmup N             unrecorded runs before measuring (default: 0)");
+    eprintln!("  --runs N               measured repetitions (default: 1)");
+    eprintln!("  --max-cv X             flag metrics whose stddev/mean exceeds X (default: 0.05)");
+    eprintln!("  --summary FILE         summary CSV (default: <out>_summary.csv)");
+    eprintln!("  --threads 1..8 | 1,2,4 pin RAYON_NUM_THREADS per run of rayon-based frameworks (kimchi, ezkl);");
+    eprintln!("                         speedup goes to <out>_scaling.csv");
+    process::exit(2);
+}
+
+// `--name value` 형태의 옵션 값
+fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+// "1..15" (양 끝 포함) 또는 "1,3,5". 4..1 처럼 비는 범위는 오류
+fn parse_list(spec: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    let list: Vec<u32> = if let Some((lo, hi)) = spec.split_once("..") {
+        let (lo, hi): (u32, u32) = (lo.parse()?, hi.parse()?);
+        (lo..=hi).collect()
+    } else {
+        spec.split(',')
+            .map(|s| s.trim().parse())
+            .collect::<Result<_, _>>()?
+    };
+    if list.is_empty() {
+        return Err(format!("{} is an empty list", spec).into());
+    }
+    Ok(list)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 || args[1].starts_with('-') {
+        usage();
+    }
+    if args[1] == "backends" {
+        return run_backends(&args).map_err(|e| e as Box<dyn Error>);
+    }
+    let suite = suite::find(&args[1]).unwrap_or_else(|| usage());
+
+    let exps = match flag_value(&args, "--exps") {
+        Some(spec) => parse_list(spec)?,
+        None => (suite.default_exps.0..=suite.default_exps.1).collect(),
+    };
+    let frameworks: Vec<&'static str> = match flag_value(&args, "--frameworks") {
+        Some(list) => {
+            let wanted: Vec<&str> = list.split(',').collect();
+            for name in &wanted {
+                if !suite.frameworks.contains(name) {
+                    return Err(format!("suite {} has no framework {}", suite.name, name).into());
+                }
+            }
+            suite.frameworks.iter().copied().filter(|f| wanted.contains(f)).collect()
+        }
+        None => suite.frameworks.to_vec(),
+    };
+    let default_out = format!("experiment_{}.csv", suite.name);
+    let out_path = flag_value(&args, "--out").unwrap_or(&default_out);
+    let log_dir = flag_value(&args, "--log-dir").unwrap_or("logs");
+    fs::create_dir_all(log_dir)?;
+    let warmup: u32 = flag_value(&args, "--warmup").map(str::parse).transpose()?.unwrap_or(0);
+    let runs: u32 = flag_value(&args, "--runs").map(str::parse).transpose()?.unwrap_or(1);
+    let max_cv: f64 = flag_value(&args, "--max-cv").map(str::parse).transpose()?.unwrap_or(0.05);
+    let default_summary = format!("{}_summary.csv", out_path.trim_end_matches(".csv"));
+    let summary_path = flag_value(&args, "--summary").unwrap_or(&default_summary);
+    if runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+    // 스레드 수를 지정하지 않으면 RAYON_NUM_THREADS 를 건드리지 않음
+    let thread_counts: Vec<Option<u32>> = match flag_value(&args, "--threads") {
+        Some(spec) => {
+            let counts = parse_list(spec)?;
+            if counts.contains(&0) {
+                return Err("--threads counts must be at least 1".into());
+            }
+            counts.into_iter().map(Some).collect()
+        }
+        None => vec![None],
+    };
+
+    for step in (suite.prepare)(&frameworks) {
+        println!("Preparing: {}", step.join(" "));
+        let status = Command::new(&step[0]).args(&step[1..]).status()?;
+        if !status.success() {
+            return Err(format!("`{}` failed with {}", step.join(" "), status).into());
+        }
+    }
+
+    let append = args.iter().any(|a| a == "--append");
+    let mut writer = if append {
+        ResultWriter::append(out_path)?
+    } else {
+        ResultWriter::create(out_path)?
+    };
+    let mut summary_writer = ResultWriter::summary(summary_path, append)?;
+    let default_scaling = format!("{}_scaling.csv", out_path.trim_end_matches(".csv"));
+    let mut scaling_writer = if thread_counts.len() > 1 {
+        Some(ResultWriter::scaling(&default_scaling, append)?)
+    } else {
+        None
+    };
+
+    for &exp_num in &exps {
+        for &framework in &frameworks {
+            let job = match (suite.job)(framework, exp_num) {
+                Some(job) => job,
+                None => continue,
+            };
+            println!("Running {} script for {} experiment {}...", framework, suite.name, exp_num);
+
+            // rayon 을 쓰지 않는 프레임워크는 스레드 수와 무관하므로 한 번만 실행
+            let framework_threads: &[Option<u32>] =
+                if thread_counts[0].is_none() || suite.rayon_frameworks.contains(&framework) {
+                    &thread_counts
+                } else {
+                    println!("  {} does not use rayon; ignoring --threads", framework);
+                    &[None]
+                };
+
+            // 스레드 수별 wall_time 평균 (speedup 계산용)
+            let mut wall_means: Vec<(u32, f64)> = vec![];
+            for &threads in framework_threads {
+                let tag = match threads {
+                    Some(n) => {
+                        println!("  threads {}", n);
+                        format!("_t{}", n)
+                    }
+                    None => String::new(),
+                };
+
+                let mut measured: Vec<Measurement> = vec![];
+                for i in 0..warmup + runs {
+                    let is_warmup = i < warmup;
+                    let label = if is_warmup {
+                        format!("warmup{}", i + 1)
+                    } else {
+                        format!("run{}", i - warmup + 1)
+                    };
+                    let log_path = format!(
+                        "{}/{}_{}{}{}_{}_log.txt",
+                        log_dir, framework, suite.name, exp_num, tag, label
+                    );
+                    let log = File::create(&log_path)?;
+                    let mut cmd = job.command();
+                    cmd.stdout(log.try_clone()?).stderr(log);
+                    if let Some(n) = threads {
+                        cmd.env("RAYON_NUM_THREADS", n.to_string());
+                    }
+
+                    let measurement = match run_measured(&mut cmd) {
+                        Ok(m) => m,
+                        Err(e) => {
+                            eprintln!("  failed to start `{}`: {}", job.command_line(), e);
+                            break;
+                        }
+                    };
+                    if !measurement.success() {
+                        eprintln!("  `{}` exited with {:?}, see {}", job.command_line(), measurement.exit_code, log_path);
+                    }
+                    if is_warmup {
+                        continue;
+                    }
+
+                    writer.write(&ResultRow {
+                        framework,
+                        model: suite.name,
+                        exp_num,
+                        run: i - warmup + 1,
+                        threads,
+                        measurement,
+                    })?;
+                    // 실패한 실행은 통계에서 제외
+                    if measurement.success() {
+                        measured.push(measurement);
+                    }
+                }
+
+                for &(metric, value) in METRICS {
+                    let samples: Vec<f64> = measured.iter().map(value).collect();
+                    let summary = match Summary::of(&samples) {
+                        Some(summary) => summary,
+                        None => continue,
+                    };
+                    if let (Some(n), "wall_time") = (threads, metric) {
+                        wall_means.push((n, summary.mean));
+                    }
+                    let noisy = summary.n > 1 && summary.is_noisy(max_cv);
+                    println!(
+                        "  {}: mean {:.3} median {:.3} stddev {:.3} 95% CI {} (n={}){}",
+                        metric,
+                        summary.mean,
+                        summary.median,
+                        summary.stddev,
+                        summary.ci95_label(),
+                        summary.n,
+                        if noisy { "  HIGH VARIANCE" } else { "" },
+                    );
+                    summary_writer.write_summary(&SummaryRow {
+                        framework,
+                        model: suite.name,
+                        exp_num,
+                        threads,
+                        metric,
+                        summary,
+                        noisy,
+                    })?;
+                }
+            }
+
+            // 첫 번째 스레드 수 기준 speedup / efficiency
+            if let (Some(writer), Some(&(base_threads, base_wall))) = (scaling_writer.as_mut(), wall_means.first()) {
+                for &(threads, wall) in &wall_means {
+                    let (speedup, efficiency) = scaling(base_wall, base_threads as usize, wall, threads as usize);
+                    println!(
+                        "  threads {}: wall_time {:.3} speedup {:.2} efficiency {:.2}",
+                        threads, wall, speedup, efficiency
+                    );
+                    writer.write_scaling(&ScalingRow {
+                        framework,
+                        model: suite.name,
+                        exp_num,
+                        threads,
+                        wall_time: wall,
+                        speedup,
+                        efficiency,
+                    })?;
+                }
+            }
+        }
+    }
+
+    println!("Experiment completed. Results saved to {} (summary: {}).", out_path, summary_path);
+    if scaling_writer.is_some() {
+        println!("Thread scaling saved to {}.", default_scaling);
+    }
+    Ok(())
+}
+
+// 모든 ZkBackend 를 같은 workload 로 실행: 각 크레이트를 빌드해 --workload 로 돌리고
+// 공통 CSV 에 한 행씩 추가한 뒤, 증명된 출력을 spec 의 기준 forward 와 비교
+fn run_backends(args: &[String]) -> Result<(), BackendError> {
+    let workload_path = match args.get(2) {
+        Some(path) if !path.starts_with('-') => fs::canonicalize(path)?,
+        _ => usage(),
+    };
+    // spec 이 없거나 레이어가 맞지 않으면 어느 backend 도 실행하지 않음
+    let workload = Workload::load(&workload_path)?;
+    let spec = workload.model()?;
+    let backends: Vec<&BackendBinary> = match flag_value(args, "--backends") {
+        Some(list) => list
+            .split(',')
+            .map(|name| {
+                suite::BACKENDS
+                    .iter()
+                    .find(|b| b.name == name)
+                    .ok_or_else(|| format!("unknown backend {}", name))
+            })
+            .collect::<Result<_, _>>()?,
+        None => suite::BACKENDS.iter().collect(),
+    };
+    let warmup = flag_value(args, "--warmup").unwrap_or("0");
+    let runs = flag_value(args, "--runs").unwrap_or("1");
+    let log_dir = flag_value(args, "--log-dir").unwrap_or("logs");
+    fs::create_dir_all(log_dir)?;
+    let log_dir = fs::canonicalize(log_dir)?;
+    let out = flag_value(args, "--out").unwrap_or("backend_results.csv");
+    let shared_out = flag_value(args, "--shared-out").unwrap_or("experiment_backends.csv");
+    // 자식 프로세스는 자기 크레이트 디렉터리에서 실행되므로 절대 경로로 넘김
+    ResultWriter::backend(out, true)?;
+    ResultWriter::shared_backend(shared_out, true)?;
+    let (out, shared_out) = (fs::canonicalize(out)?, fs::canonicalize(shared_out)?);
+
+    let mut ran = vec![];
+    for backend in backends {
+        let build = backend.build();
+        println!("Preparing: {}", build.join(" "));
+        let status = Command::new(&build[0]).args(&build[1..]).status()?;
+        if !status.success() {
+            eprintln!("  {} is not available (build failed with {}), skipped", backend.name, status);
+            continue;
+        }
+
+        let outputs_path = log_dir.join(format!("{}_{}_outputs.json", backend.name, workload.name));
+        let extra: Vec<String> = [
+            ("--warmup", warmup.to_string()),
+            ("--runs", runs.to_string()),
+            ("--out", out.display().to_string()),
+            ("--shared-out", shared_out.display().to_string()),
+            ("--dump-outputs", outputs_path.display().to_string()),
+        ]
+        .into_iter()
+        .flat_map(|(flag, value)| [flag.to_string(), value])
+        .collect();
+        let job = backend.job(&workload_path, &extra);
+        println!("Running {} on workload {}...", backend.name, workload.name);
+        let status = job.command().status()?;
+        if !status.success() {
+            eprintln!("  `{}` exited with {}", job.command_line(), status);
+            continue;
+        }
+        ran.push((backend.name, outputs_path));
+    }
+
+    // 기준 출력과의 최대 절대 오차 (백엔드 간 비교는 equivcheck 로 더 자세히)
+    let reference: Vec<Vec<f64>> = workload.input.iter().map(|row| spec.forward(row)).collect();
+    println!("backend,max_abs_error_vs_reference");
+    for (name, path) in &ran {
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let outputs: Vec<Vec<f64>> = serde_json::from_value(json["outputs"].clone())?;
+        if outputs.len() != reference.len() || outputs.iter().zip(&reference).any(|(o, r)| o.len() != r.len()) {
+            println!("{},shape mismatch", name);
+            continue;
+        }
+        let max_abs = outputs
+            .iter()
+            .flatten()
+            .zip(reference.iter().flatten())
+            .map(|(got, want)| (got - want).abs())
+            .fold(0.0, f64::max);
+        println!("{},{:.3e}", name, max_abs);
+    }
+    println!("Backend results appended to {} and {}.", out.display(), shared_out.display());
+    if ran.is_empty() {
+        return Err("no backend ran".into());
+    }
+    Ok(())
+}
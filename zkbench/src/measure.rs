@@ -0,0 +1,108 @@
+//! Runs a command and measures wall time, user/sys CPU time and peak RSS from
+//! `wait4` rusage, so we no longer depend on `gtime -v`.
+use std::io;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Measurement {
+    pub wall: Duration,
+    pub user: Duration,
+    pub sys: Duration,
+    /// Peak resident set size in KiB (same unit gtime reported).
+    pub max_rss_kb: u64,
+    /// `None` when the process was killed by a signal.
+    pub exit_code: Option<i32>,
+}
+
+impl Measurement {
+    /// CPU utilisation in percent, like gtime's "Percent of CPU this job got".
+    pub fn cpu_percent(&self) -> f64 {
+        let wall = self.wall.as_secs_f64();
+        if wall == 0.0 {
+            return 0.0;
+        }
+        (self.user + self.sys).as_secs_f64() / wall * 100.0
+    }
+
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+// ru_maxrss 단위: Linux는 KiB, macOS는 byte
+fn rss_kb(usage: &libc::rusage) -> u64 {
+    let max_rss = usage.ru_maxrss as u64;
+    if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    }
+}
+
+fn timeval(tv: libc::timeval) -> Duration {
+    Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64)
+}
+
+/// Spawns `cmd`, waits for it and returns its resource usage.
+///
+/// Usage covers the child and every descendant it waited for (e.g. the binary
+/// started by `cargo run`).
+pub fn run_measured(cmd: &mut Command) -> io::Result<Measurement> {
+    let start = Instant::now();
+    let child = cmd.spawn()?;
+    let pid = child.id() as libc::pid_t;
+
+    let mut status: libc::c_int = 0;
+    // SAFETY: rusage is plain old data, all-zero is a valid value
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: pid is our own child which has not been reaped yet
+        let ret = unsafe { libc::wait4(pid, &mut status, 0, &mut usage) };
+        if ret == pid {
+            break;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+    let wall = start.elapsed();
+
+    let max_rss_kb = rss_kb(&usage);
+
+    Ok(Measurement {
+        wall,
+        user: timeval(usage.ru_utime),
+        sys: timeval(usage.ru_stime),
+        max_rss_kb,
+        exit_code: if libc::WIFEXITED(status) {
+            Some(libc::WEXITSTATUS(status))
+        } else {
+            None
+        },
+    })
+}
+
+fn self_usage() -> Option<libc::rusage> {
+    // SAFETY: rusage is plain old data, all-zero is a valid value
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    // SAFETY: RUSAGE_SELF with a valid out pointer
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) } != 0 {
+        return None;
+    }
+    Some(usage)
+}
+
+/// Peak resident set size of the calling process so far, in KiB, for in-process
+/// benchmarks that run one configuration per process.
+pub fn peak_rss_kb() -> u64 {
+    self_usage().map(|usage| rss_kb(&usage)).unwrap_or(0)
+}
+
+/// User + system CPU time of the calling process (all threads) so far.
+pub fn cpu_time() -> Duration {
+    self_usage()
+        .map(|usage| timeval(usage.ru_utime) + timeval(usage.ru_stime))
+        .unwrap_or_default()
+}
@@ -0,0 +1,196 @@
+//! Summary statistics over repeated runs.
+
+/// Two-sided 95% Student t critical values for 1..=30 degrees of freedom.
+const T95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Tabulated values past 30 degrees of freedom; the normal 1.960 is the limit.
+const T95_TAIL: [(usize, f64); 4] = [(30, 2.042), (40, 2.021), (60, 2.000), (120, 1.980)];
+
+fn t95(df: usize) -> f64 {
+    match df {
+        0 => f64::NAN,
+        1..=30 => T95[df - 1],
+        _ => {
+            // 표 사이는 1/df 에 대해 선형 보간, 120 이후는 1.960 (1/df = 0) 으로 수렴
+            let upper = T95_TAIL.iter().position(|&(d, _)| d >= df);
+            let (d0, t0) = T95_TAIL[upper.unwrap_or(T95_TAIL.len()) - 1];
+            let (x1, t1) = match upper {
+                Some(i) => (1.0 / T95_TAIL[i].0 as f64, T95_TAIL[i].1),
+                None => (0.0, 1.960),
+            };
+            let x0 = 1.0 / d0 as f64;
+            t0 + (t1 - t0) * (1.0 / df as f64 - x0) / (x1 - x0)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Summary {
+    pub n: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation (n - 1).
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// 95% confidence interval of the mean; `None` for a single sample.
+    pub ci95: Option<(f64, f64)>,
+}
+
+impl Summary {
+    /// `None` for an empty sample.
+    pub fn of(samples: &[f64]) -> Option<Self> {
+        let n = samples.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let stddev = if n > 1 {
+            (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let ci95 = if n > 1 {
+            let half_width = t95(n - 1) * stddev / (n as f64).sqrt();
+            Some((mean - half_width, mean + half_width))
+        } else {
+            None
+        };
+
+        Some(Summary {
+            n,
+            mean,
+            median,
+            stddev,
+            min: sorted[0],
+            max: sorted[n - 1],
+            ci95,
+        })
+    }
+
+    /// Coefficient of variation (stddev / mean).
+    pub fn cv(&self) -> f64 {
+        if self.mean == 0.0 {
+            0.0
+        } else {
+            self.stddev / self.mean
+        }
+    }
+
+    pub fn is_noisy(&self, max_cv: f64) -> bool {
+        self.cv() > max_cv
+    }
+
+    /// `[low, high]` of the 95% CI, or `n/a` for a single sample.
+    pub fn ci95_label(&self) -> String {
+        match self.ci95 {
+            Some((low, high)) => format!("[{:.3}, {:.3}]", low, high),
+            None => "n/a".to_string(),
+        }
+    }
+}
+
+/// Speedup and parallel efficiency of `time` on `threads` relative to
+/// `base_time` on `base_threads` (normally 1, giving the usual T1/Tn and T1/(n*Tn)).
+pub fn scaling(base_time: f64, base_threads: usize, time: f64, threads: usize) -> (f64, f64) {
+    let speedup = base_time / time;
+    (speedup, speedup * base_threads as f64 / threads as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn mean_median_and_sample_stddev() {
+        let s = Summary::of(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(s.n, 8);
+        assert!(close(s.mean, 5.0));
+        assert!(close(s.median, 4.5));
+        // 편차 제곱합 32, n - 1 = 7
+        assert!(close(s.stddev, (32.0f64 / 7.0).sqrt()));
+        assert_eq!((s.min, s.max), (2.0, 9.0));
+        assert!(close(s.cv(), s.stddev / 5.0));
+    }
+
+    #[test]
+    fn ci_for_two_samples_uses_df_1() {
+        let s = Summary::of(&[1.0, 3.0]).unwrap();
+        // stddev sqrt(2), half width 12.706 * sqrt(2) / sqrt(2)
+        let (low, high) = s.ci95.unwrap();
+        assert!(close(low, 2.0 - 12.706));
+        assert!(close(high, 2.0 + 12.706));
+    }
+
+    #[test]
+    fn ci_for_thirty_samples_uses_df_29() {
+        let samples: Vec<f64> = (1..=30).map(f64::from).collect();
+        let s = Summary::of(&samples).unwrap();
+        assert!(close(s.mean, 15.5));
+        // 1..=n 의 표본 분산은 n(n+1)/12
+        assert!(close(s.stddev, (30.0f64 * 31.0 / 12.0).sqrt()));
+        let half_width = 2.045 * s.stddev / 30f64.sqrt();
+        let (low, high) = s.ci95.unwrap();
+        assert!(close(low, 15.5 - half_width));
+        assert!(close(high, 15.5 + half_width));
+    }
+
+    #[test]
+    fn single_sample_has_no_ci() {
+        let s = Summary::of(&[4.2]).unwrap();
+        assert_eq!(s.stddev, 0.0);
+        assert!(s.ci95.is_none());
+        assert_eq!(s.ci95_label(), "n/a");
+        assert!(!s.is_noisy(0.05));
+    }
+
+    #[test]
+    fn empty_sample_has_no_summary() {
+        assert!(Summary::of(&[]).is_none());
+    }
+
+    #[test]
+    fn t_table_exact_values() {
+        assert_eq!(t95(1), 12.706);
+        assert_eq!(t95(30), 2.042);
+        assert!(t95(0).is_nan());
+    }
+
+    #[test]
+    fn t_table_interpolates_past_30() {
+        assert!(close(t95(40), 2.021));
+        assert!(close(t95(60), 2.000));
+        assert!(close(t95(120), 1.980));
+        // 실제 값: df 31 = 2.040, 50 = 2.009, 100 = 1.984, 1000 = 1.962
+        assert!((t95(31) - 2.040).abs() < 1e-3);
+        assert!((t95(50) - 2.009).abs() < 1e-3);
+        assert!((t95(100) - 1.984).abs() < 1e-3);
+        assert!((t95(1000) - 1.962).abs() < 1e-3);
+        // 단조 감소
+        assert!((1..500).all(|df| t95(df + 1) <= t95(df)));
+    }
+
+    #[test]
+    fn noisy_above_max_cv() {
+        let s = Summary::of(&[1.0, 3.0]).unwrap();
+        assert!(s.is_noisy(0.5));
+        assert!(!s.is_noisy(1.0));
+    }
+}
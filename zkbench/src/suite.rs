@@ -0,0 +1,162 @@
+//! Benchmark suites, mirroring what `linux_bench.sh`, `bench.sh` and
+//! `recursion.sh` used to run. Paths are relative to the repository root.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// One framework command for one experiment.
+pub struct Job {
+    pub framework: &'static str,
+    pub exp_num: u32,
+    pub program: String,
+    pub args: Vec<String>,
+    pub dir: Option<PathBuf>,
+}
+
+impl Job {
+    fn new(framework: &'static str, exp_num: u32, argv: &[String]) -> Self {
+        Job {
+            framework,
+            exp_num,
+            program: argv[0].clone(),
+            args: argv[1..].to_vec(),
+            dir: None,
+        }
+    }
+
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+        if let Some(dir) = &self.dir {
+            cmd.current_dir(dir);
+        }
+        cmd
+    }
+
+    pub fn command_line(&self) -> String {
+        let mut line = self.program.clone();
+        for arg in &self.args {
+            line.push(' ');
+            line.push_str(arg);
+        }
+        line
+    }
+}
+
+pub struct Suite {
+    pub name: &'static str,
+    pub frameworks: &'static [&'static str],
+    /// Frameworks whose provers use rayon, so `RAYON_NUM_THREADS` sets their thread count.
+    pub rayon_frameworks: &'static [&'static str],
+    pub default_exps: (u32, u32),
+    /// Commands to run once before measuring (builds), not measured.
+    pub prepare: fn(&[&str]) -> Vec<Vec<String>>,
+    pub job: fn(&'static str, u32) -> Option<Job>,
+}
+
+fn argv(line: String) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+fn ezkl_prove(dir: &str, i: u32) -> Vec<String> {
+    argv(format!(
+        "ezkl prove --witness models/{dir}/mlp{i}/witness.json --pk-path models/{dir}/mlp{i}/pk.key \
+         --compiled-circuit models/{dir}/mlp{i}/model.compiled --proof-path models/{dir}/mlp{i}/proof.json"
+    ))
+}
+
+fn mlp_prepare(frameworks: &[&str]) -> Vec<Vec<String>> {
+    let mut steps = vec![];
+    if frameworks.contains(&"kimchi") {
+        steps.push(argv("cargo build --release --manifest-path kimchi_exp/Cargo.toml".to_string()));
+    }
+    steps
+}
+
+fn mlp_job(framework: &'static str, i: u32) -> Option<Job> {
+    let argv = match framework {
+        "ezkl" => ezkl_prove("mlp", i),
+        "o1js" => argv(format!("node dist/mlp.js {}", i)),
+        "kimchi" => argv(format!("kimchi_exp/target/release/kimchi_exp {}", i)),
+        _ => return None,
+    };
+    Some(Job::new(framework, i, &argv))
+}
+
+fn recursion_prepare(_frameworks: &[&str]) -> Vec<Vec<String>> {
+    vec![]
+}
+
+fn recursion_job(framework: &'static str, i: u32) -> Option<Job> {
+    let argv = match framework {
+        "ezkl" => ezkl_prove("recursion", i),
+        "o1js" => argv(format!("node dist/mlp_recursion.js {}", i)),
+        _ => return None,
+    };
+    Some(Job::new(framework, i, &argv))
+}
+
+pub const SUITES: &[Suite] = &[
+    Suite {
+        name: "mlp",
+        frameworks: &["ezkl", "o1js", "kimchi"],
+        rayon_frameworks: &["ezkl", "kimchi"],
+        default_exps: (1, 15),
+        prepare: mlp_prepare,
+        job: mlp_job,
+    },
+    Suite {
+        name: "recursion",
+        frameworks: &["ezkl", "o1js"],
+        rayon_frameworks: &["ezkl"],
+        default_exps: (1, 12),
+        prepare: recursion_prepare,
+        job: recursion_job,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Suite> {
+    SUITES.iter().find(|s| s.name == name)
+}
+
+/// A crate whose binary runs one `ZkBackend` on a workload file (`--workload`).
+pub struct BackendBinary {
+    pub name: &'static str,
+    /// Crate directory; the binary runs from here so its own relative paths resolve.
+    pub dir: &'static str,
+    /// Binary path relative to `dir`.
+    pub binary: &'static str,
+}
+
+impl BackendBinary {
+    pub fn build(&self) -> Vec<String> {
+        argv(format!("cargo build --release --manifest-path {}/Cargo.toml", self.dir))
+    }
+
+    /// Proves `workload` with `extra` options (`--runs`, `--out`, ...); paths must be absolute.
+    pub fn job(&self, workload: &Path, extra: &[String]) -> Job {
+        // 자식은 dir 에서 실행되므로 바이너리 경로는 절대 경로로 넘김
+        let binary = std::env::current_dir().unwrap_or_default().join(self.dir).join(self.binary);
+        let mut argv = vec![
+            binary.display().to_string(),
+            "--workload".to_string(),
+            workload.display().to_string(),
+        ];
+        argv.extend_from_slice(extra);
+        let mut job = Job::new(self.name, 0, &argv);
+        job.dir = Some(PathBuf::from(self.dir));
+        job
+    }
+}
+
+pub const BACKENDS: &[BackendBinary] = &[
+    BackendBinary {
+        name: "kimchi",
+        dir: "kimchi_exp",
+        binary: "target/release/kimchi_exp",
+    },
+    BackendBinary {
+        name: "risc0",
+        dir: "mlp_risczero",
+        binary: "target/release/host",
+    },
+];